@@ -0,0 +1,3419 @@
+use crate::assets::SpriteSheet;
+use crate::audio::{SoundClip, open_output_stream, play_tone};
+use crate::config::{
+    AppConfig, MAX_TILT_DEGREES, MAX_UI_SCALE, MIN_UI_SCALE, SegmentConfig, ValidationReport,
+    apply_probabilities, resolve_path,
+};
+use crate::geometry::{SliceBounds, WheelGeometry};
+use crate::helpers::{
+    circle_image_mesh, color_to_hex, cubic_bezier, deg_to_rad, format_number_label, format_odds,
+    generate_deterministic_color, is_bright, is_valid_bezier, parse_boundary_tiebreak,
+    parse_hex_color, parse_label_direction, parse_layout, parse_mouse_button, parse_odds_format,
+    parse_segment_sort, parse_theme, parse_tick_mode, parse_winner_position, pie_slice_points,
+    pointer_triangle, slice_pattern_shapes, truncate_with_ellipsis,
+};
+use eframe::egui;
+use eframe::egui::accesskit;
+use eframe::{App, Frame};
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::{Rng, SeedableRng};
+use rodio::{OutputStream, OutputStreamHandle, Sink};
+use std::borrow::Cow;
+use std::f32::consts::PI;
+use std::fs;
+use std::io::Write;
+use std::sync::mpsc;
+
+const AVERAGE_SPIN_TURNS: f32 = 12.0;
+// Angle travelled between two ticks in velocity tick mode
+const VELOCITY_TICK_ANGLE: f32 = 2.0 * PI / 16.0;
+const ZOOM_REVEAL_SECONDS: f32 = 0.6;
+const ZOOM_REVEAL_SCALE: f32 = 0.3;
+const SNAP_SECONDS: f32 = 0.4;
+const WINDUP_SECONDS: f32 = 0.35;
+const WINDUP_ANGLE: f32 = 8.0 * PI / 180.0;
+const SETTLE_SECONDS: f32 = 0.4;
+const SETTLE_AMPLITUDE: f32 = 3.0 * PI / 180.0;
+const STATUS_WRITE_INTERVAL: f32 = 0.1;
+const ELIMINATE_SECONDS: f32 = 0.6;
+// Speed (rad/s) at which motion blur is at full strength, and how far back in
+// time its trailing copies reach
+const BLUR_FULL_VELOCITY: f32 = 6.0 * PI;
+const BLUR_SECONDS: f32 = 0.03;
+// Arc resolution of the slices, and the longest straight piece of the outer
+// edge in high quality mode (points)
+const ARC_STEPS_PER_RADIAN: f32 = 15.0;
+const HIGH_QUALITY_CHORD: f32 = 1.5;
+// Time the winner of a chaining segment is shown before the next wheel spins
+const CHAIN_DELAY_SECONDS: f32 = 1.5;
+// Most chained spins in a row, in case the wheels chain in a circle
+const MAX_CHAIN_DEPTH: u32 = 8;
+// Ticker scroll speed, in points per second
+const TICKER_SPEED: f32 = 60.0;
+const TICKER_FONT_SIZE: f32 = 18.0;
+// Largest label size of the picker layout, shrunk to fit long names
+const PICKER_FONT_SIZE: f32 = 96.0;
+// Height of an average segment on the reel strip
+const REEL_ROW_HEIGHT: f32 = 56.0;
+// Extra tick loudness at the very end of a spin with tension ticks, 3.0 = 4x
+const TENSION_GAIN: f32 = 3.0;
+// Background music volume, normally and while the wheel spins
+const MUSIC_VOLUME: f32 = 0.5;
+const MUSIC_DUCKED_VOLUME: f32 = 0.15;
+// Confetti pieces per burst, how long they last, and their fall in wheel radii
+const CONFETTI_COUNT: usize = 80;
+const CONFETTI_SECONDS: f32 = 2.5;
+const CONFETTI_GRAVITY: f32 = 1.6;
+// Playback volume of an average tick sample, before jitter
+const TICK_SAMPLE_VOLUME: f32 = 0.5;
+// Slowest release (rad/s) that still counts as a flick in drag spin mode
+const MIN_FLICK_VELOCITY: f32 = PI;
+const MAX_FLICK_VELOCITY: f32 = 12.0 * PI;
+// Constant part of the drag spin deceleration (rad/s²) so the wheel comes to a
+// full stop instead of creeping along forever under proportional friction alone
+const BASE_DECELERATION: f32 = 0.5;
+// Hold time that fully charges a power spin, and the turns at no and full charge
+const POWER_CHARGE_SECONDS: f32 = 1.5;
+const POWER_MIN_TURNS: f32 = 3.0;
+const POWER_MAX_TURNS: f32 = 20.0;
+// Longest spin with reduced motion, the wheel turns less than once in it
+const REDUCED_MOTION_SPIN_MS: f32 = 800.0;
+
+// --- RUNTIME STRUCTS ---
+
+#[derive(Clone, Copy, PartialEq)]
+pub(crate) enum LabelDirection {
+    Ltr,
+    Rtl,
+    Vertical,
+}
+
+/// The outcome of a completed spin, as delivered to a result channel.
+#[derive(Clone, Debug, serde::Serialize)]
+pub struct WinnerResult {
+    pub label: String,
+    pub index: usize,
+    pub weight: u32,
+}
+
+/// How often one segment won, against how often its weight says it should.
+/// Shares are fractions of all wins and of the total weight.
+#[derive(Clone, Debug, serde::Serialize)]
+pub struct SegmentStats {
+    pub label: String,
+    pub weight: u32,
+    pub hits: u32,
+    pub expected_share: f32,
+    pub observed_share: f32,
+}
+
+/// Win counts for the whole session, as written by `--dump-stats`.
+#[derive(Clone, Debug, serde::Serialize)]
+pub struct SpinStats {
+    /// Name of the wheel, set when several wheels share one window
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub wheel: Option<String>,
+    pub spins: u32,
+    pub segments: Vec<SegmentStats>,
+}
+
+// How the segments are presented. All share the spin, which turns `rotation`;
+// the reel maps one full turn to one pass of the strip, and the picker shows
+// only the segment under the pointer.
+#[derive(Clone, Copy, PartialEq)]
+pub(crate) enum Layout {
+    Wheel,
+    Reel,
+    Picker,
+}
+
+#[derive(Clone, Copy, PartialEq)]
+pub(crate) enum WinnerPosition {
+    Center,
+    Top,
+    Bottom,
+    BelowWheel,
+}
+
+#[derive(Clone, Copy, PartialEq)]
+pub(crate) enum BoundaryTiebreak {
+    Lower,
+    Higher,
+    Random,
+}
+
+#[derive(Clone, Copy, PartialEq)]
+pub(crate) enum SegmentSort {
+    Config,
+    Label,
+    WeightAsc,
+    WeightDesc,
+}
+
+#[derive(Clone, Copy, PartialEq)]
+pub(crate) enum OddsFormat {
+    Percent,
+    Fraction,
+    Ratio,
+}
+
+#[derive(Clone, Copy, PartialEq)]
+pub(crate) enum TickMode {
+    Boundary,
+    Velocity,
+}
+
+// Background of the winner message, drawn once the text's position is known
+struct WinnerCard {
+    fill: egui::Color32,
+    border: Option<egui::Color32>,
+    padding: f32,
+}
+
+// Effects fired a set time after a winner is revealed
+#[derive(Clone, Copy)]
+enum RevealEffect {
+    Confetti,
+    Fanfare,
+}
+
+// One falling piece of confetti. Position and velocity are in wheel radii from
+// the wheel center, so the burst scales with the window.
+struct ConfettiPiece {
+    pos: egui::Vec2,
+    velocity: egui::Vec2,
+    color: egui::Color32,
+    angle: f32,
+    spin: f32,
+    elapsed: f32,
+}
+
+// A short post-spin rotation that centers the winning slice under the pointer
+struct SnapAnimation {
+    from: f32,
+    to: f32,
+    elapsed: f32,
+}
+
+// An eliminated slice fading to gray and shrinking into the hub, drawn where it
+// was before the layout closed the gap
+struct RemovingSegment {
+    bounds: SliceBounds,
+    color: egui::Color32,
+    elapsed: f32,
+}
+
+struct ProcessedSegment {
+    label: String,
+    weight: u32,
+    color: egui::Color32,
+    font_size: Option<f32>,
+    is_blank: bool,
+    tags: Vec<String>,
+    image_path: Option<String>,
+    image: Option<SpriteSheet>,
+    chain_to: Option<String>,
+    // As configured, the weight is derived from it
+    probability: Option<f32>,
+}
+
+pub struct OverlayApp {
+    layout: Layout,
+
+    // Spin animation
+    rotation: f32,
+    start_rotation: f32,
+    target_rotation: f32,
+    current_spin_time: f32,
+    spin_duration_ms: f32,
+    // Unpaused time since the spin started, against `max_spin_seconds`
+    spin_elapsed: f32,
+    max_spin_seconds: Option<f32>,
+    scale_duration_with_spins: bool,
+    reduced_motion: bool,
+    power_spin: bool,
+    // Charge (0..=1) of the power meter while Space is held
+    power_charge: Option<f32>,
+    active_spin_duration_ms: f32,
+    easing_bezier: Option<[f32; 4]>,
+    is_spinning: bool,
+    // A running spin frozen in place, resumed exactly where it stopped
+    paused: bool,
+    snap_winner_to_top: bool,
+    settle_bounce: bool,
+    settle_elapsed: Option<f32>,
+    windup: bool,
+    // Time into the wind-up before a timed spin, `None` once it has launched
+    windup_elapsed: Option<f32>,
+    reveal_delay: f32,
+    reveal_timer: Option<f32>,
+    snap_animation: Option<SnapAnimation>,
+    auto_spin_timer: Option<f32>,
+    auto_spin_interval: Option<f32>,
+    auto_spin_paused: bool,
+    pointer_angle: f32,
+    // Every pointer including the main one, empty with a single pointer
+    pointer_angles: Vec<f32>,
+    click_to_spin: bool,
+    click_hub_only: bool,
+    anchor_first_segment: bool,
+    winner_in_center: bool,
+    spin_mouse_button: egui::PointerButton,
+    drag_spin: bool,
+    friction: f32,
+    // Pointer angle around the hub during a drag, and the smoothed drag speed
+    drag_angle: Option<f32>,
+    drag_velocity: f32,
+    // Angular velocity of a flicked wheel coasting to a stop
+    momentum: Option<f32>,
+    flick_velocity: f32,
+
+    // Audio
+    // Both missing when no output device could be opened
+    _audio_stream: Option<OutputStream>,
+    audio_handle: Option<OutputStreamHandle>,
+    last_segment_index: Option<usize>,
+    tick_mode: TickMode,
+    boundary_tiebreak: BoundaryTiebreak,
+    // The random side taken for a spin that ended on a boundary
+    boundary_choice: Option<usize>,
+    // Smallest turn between two boundary ticks, from `ticks_per_revolution`
+    min_tick_angle: f32,
+    last_tick_rotation: Option<f32>,
+    tick_accumulator: f32,
+    tension_ticks: bool,
+    tick_sounds: Vec<SoundClip>,
+    countdown_cues: bool,
+    next_countdown_cue: u32,
+    countdown_sound: Option<SoundClip>,
+    drumroll_sound: Option<SoundClip>,
+    drumroll_sink: Option<Sink>,
+    start_sound: Option<SoundClip>,
+    loop_sound: Option<SoundClip>,
+    loop_sink: Option<Sink>,
+    lock_sound: Option<SoundClip>,
+    fanfare_sound: Option<SoundClip>,
+    fanfare_delay: f32,
+    music_sink: Option<Sink>,
+
+    // Visuals
+    // Shortest time between two animation frames, `None` repaints every vsync
+    frame_interval: Option<f32>,
+    max_frame_dt: f32,
+    // Frame time beyond `max_frame_dt` not yet fed to the animation, `None`
+    // after an idle frame since the time since then wasn't animated anyway
+    frame_debt: Option<f32>,
+    background: Option<SpriteSheet>,
+    center_color: egui::Color32,
+    center_radius_ratio: f32,
+    center_offset: egui::Vec2,
+    winner_templates: Vec<String>,
+    winners_per_spin: usize,
+    blank_message: String,
+    active_template: usize,
+    template_notice_timer: f32,
+    winner_font_size: f32,
+    winner_card: Option<WinnerCard>,
+    winner_position: WinnerPosition,
+    label_font_size: f32,
+    label_direction: LabelDirection,
+    label_shadow: bool,
+    label_bg_color: Option<egui::Color32>,
+    label_number_format: Option<String>,
+    label_max_chars: Option<usize>,
+    label_wrap_width: Option<f32>,
+    mystery_mode: bool,
+    max_labels: Option<usize>,
+    show_segments_borders: bool,
+    // Drawn gap between slices, radians
+    segment_gap: f32,
+    colorblind_patterns: bool,
+    motion_blur: bool,
+    // Speed of the wheel over the last step, rad/s
+    angular_velocity: f32,
+    show_rim: bool,
+    rim_color: egui::Color32,
+    rim_thickness: f32,
+    // Vertical scale of the tilted wheel, 1.0 when flat
+    tilt_scale: f32,
+    show_legend: bool,
+    odds_format: OddsFormat,
+    show_slice_odds: bool,
+    // Applied on the first frame, `None` keeps egui's own choice
+    ui_theme: Option<egui::Theme>,
+    high_quality: bool,
+    // Tessellation settings still to be applied to the context
+    high_quality_pending: bool,
+    // Likewise applied once, `None` keeps the detected scaling
+    ui_scale: Option<f32>,
+    show_remaining_count: bool,
+    show_ticker: bool,
+    show_fairness_info: bool,
+    // Scroll distance of the ticker, wrapped to the text width when drawn
+    ticker_offset: f32,
+    zoom_reveal: bool,
+    confetti: bool,
+    confetti_delay: f32,
+    confetti_pieces: Vec<ConfettiPiece>,
+    // Reveal effects still to fire and the time left until each
+    scheduled: Vec<(f32, RevealEffect)>,
+    zoom_timer: f32,
+    start_hint: String,
+    title_text: String,
+    title_font_size: f32,
+
+    // Data
+    segments: Vec<ProcessedSegment>,
+    reverse_segment_order: bool,
+    segment_sort: SegmentSort,
+    // Weight of the segments currently on the wheel
+    total_weight: u32,
+    // Every tag in config order, and the one the wheel is filtered on
+    tag_filters: Vec<String>,
+    tag_filter: Option<usize>,
+    // Minimum drawn slice width as a fraction of a turn
+    min_slice_fraction: f32,
+    geometry: WheelGeometry,
+    elimination_mode: bool,
+    eliminate_animation: bool,
+    removing: Vec<RemovingSegment>,
+    fair_bag: bool,
+    // Source of every random choice that decides a result, seeded by `seed`
+    rng: StdRng,
+    // Tick sample and jitter choices, a separate stream from the same seed
+    tick_rng: StdRng,
+    seed: Option<u64>,
+    // Remaining segment tickets in fair bag mode, drawn from the end
+    bag: Vec<usize>,
+    winning_label: Option<String>,
+    winning_index: Option<usize>,
+    // Every winner of the last spin, eliminated together in elimination mode
+    winning_indices: Vec<usize>,
+    spin_count: u32,
+    // The current or last spin is a practice one, and whether the next is
+    practice: bool,
+    practice_next: bool,
+    max_spins_allowed: Option<u32>,
+    spin_cooldown: f32,
+    // Time left before the next spin may start, and whether a trigger was ignored
+    cooldown_remaining: f32,
+    cooldown_notice: bool,
+    // Spins in the chain leading to the current one, 0 for a spin started directly
+    chain_depth: u32,
+    // Wheel to spin next and the time left until then
+    pending_chain: Option<(String, f32)>,
+    // Every winner so far in the order they were drawn, for the summary
+    history: Vec<String>,
+    show_summary: bool,
+    // Wins per segment as (label, weight, hits), kept through eliminations
+    hit_counts: Vec<(String, u32, u32)>,
+    show_stats: bool,
+    stats_path: Option<String>,
+
+    // Embedding
+    // The config the app was started with, the base for exporting the runtime state
+    source_config: AppConfig,
+    result_sender: Option<mpsc::Sender<WinnerResult>>,
+    status_file: Option<String>,
+    audit_log: Option<String>,
+    #[cfg_attr(not(feature = "webhook"), allow(dead_code))]
+    webhook_url: Option<String>,
+    on_win_command: Option<String>,
+    // Random inputs of the current spin, written out with the winner
+    spin_audit: Option<String>,
+    status_elapsed: f32,
+    last_status: Option<String>,
+}
+
+impl OverlayApp {
+    /// Builds the app, printing any config problems.
+    ///
+    /// # Panics
+    ///
+    /// With `strict_assets` set, if any asset file is missing. Use
+    /// [`Self::try_new`] to handle that instead.
+    pub fn new(config: AppConfig) -> Self {
+        Self::try_new(config).unwrap_or_else(|report| {
+            report.print();
+            panic!("strict_assets is set and asset files are missing");
+        })
+    }
+
+    /// Builds the app, printing any config problems, or returns the
+    /// validation report when `strict_assets` is set and asset files are missing.
+    pub fn try_new(config: AppConfig) -> Result<Self, ValidationReport> {
+        config.validate_strict()?.print();
+        Ok(Self::from_config(config))
+    }
+
+    // `new` without reporting config problems
+    pub(crate) fn from_config(config: AppConfig) -> Self {
+        let (stream, stream_handle) = open_output_stream(config.audio_device.as_deref()).unzip();
+        Self::with_audio(config, stream, stream_handle)
+    }
+
+    // `from_config` playing through an output stream opened elsewhere, for wheels
+    // sharing a validated config and one stream. `stream` is kept open by this app.
+    pub(crate) fn with_audio(
+        mut config: AppConfig,
+        _stream: Option<OutputStream>,
+        stream_handle: Option<OutputStreamHandle>,
+    ) -> Self {
+        let source_config = config.clone();
+        apply_probabilities(&mut config.segments);
+        let mut tag_filters: Vec<String> = Vec::new();
+        for tag in config.segments.iter().flat_map(|s| s.tags.iter().flatten()) {
+            if !tag_filters.contains(tag) {
+                tag_filters.push(tag.clone());
+            }
+        }
+
+        let max_weight = config.segments.iter().map(|s| s.weight).max().unwrap_or(0);
+        let weight_affects_color = config.weight_affects_color.unwrap_or(false);
+        let reduced_motion = config.reduced_motion.unwrap_or(false);
+        let base_dir = config.base_dir.clone();
+        let asset = |path: &String| resolve_path(base_dir.as_deref(), path);
+
+        let segments: Vec<ProcessedSegment> = config
+            .segments
+            .into_iter()
+            .map(|s| {
+                // Only generated colors reflect the weight, explicit colors are kept as-is
+                let intensity = if weight_affects_color && max_weight > 0 {
+                    s.weight as f32 / max_weight as f32
+                } else {
+                    1.0
+                };
+                let color = s
+                    .color
+                    .as_deref()
+                    .and_then(parse_hex_color)
+                    .unwrap_or_else(|| generate_deterministic_color(&s.label, intensity));
+
+                ProcessedSegment {
+                    label: s.label,
+                    weight: s.weight,
+                    color,
+                    font_size: s.font_size,
+                    is_blank: s.is_blank.unwrap_or(false),
+                    tags: s.tags.unwrap_or_default(),
+                    image: s
+                        .image
+                        .as_ref()
+                        .and_then(|path| SpriteSheet::load(&asset(path), 1, 1, 0.0)),
+                    image_path: s.image,
+                    chain_to: s.chain_to,
+                    probability: s.probability,
+                }
+            })
+            .collect();
+
+        let center_color = config
+            .center_color
+            .as_deref()
+            .and_then(parse_hex_color)
+            .unwrap_or(egui::Color32::from_gray(32));
+
+        let center_radius_ratio = config.center_radius_ratio.unwrap_or(0.2).clamp(0.0, 0.8);
+
+        // Process winner configuration
+        let winner_templates = match config.winner_messages {
+            Some(messages) if !messages.is_empty() => messages,
+            _ => vec![
+                config
+                    .winner_message
+                    .unwrap_or_else(|| "Winner:\n{label}".to_string()),
+            ],
+        };
+
+        let winner_font_size = config.winner_font_size.unwrap_or(40.0);
+        let label_font_size = config.label_font_size.unwrap_or(20.0);
+        let label_direction = config
+            .label_direction
+            .as_deref()
+            .and_then(parse_label_direction)
+            .unwrap_or(LabelDirection::Ltr);
+        let show_segments_borders = config.show_segments_borders.unwrap_or(true);
+        let background = match (&config.background_spritesheet, &config.background_image) {
+            (Some(path), _) => {
+                let frames = config.bg_frame_count.unwrap_or(1);
+                SpriteSheet::load(
+                    &asset(path),
+                    frames,
+                    config.bg_columns.unwrap_or(frames),
+                    config.bg_fps.unwrap_or(12.0),
+                )
+            }
+            (None, Some(path)) => SpriteSheet::load(&asset(path), 1, 1, 0.0),
+            (None, None) => None,
+        };
+
+        let rim_color = config
+            .rim_color
+            .as_deref()
+            .and_then(parse_hex_color)
+            .unwrap_or(egui::Color32::from_black_alpha(220));
+        let show_legend = config.show_legend.unwrap_or(false);
+        let start_hint = config
+            .start_hint
+            .unwrap_or_else(|| "Press SPACE to spin".to_string());
+
+        let mut rng = match config.seed {
+            Some(seed) => StdRng::seed_from_u64(seed),
+            None => StdRng::from_os_rng(),
+        };
+        // Ticks follow the frame rate, drawing them from `rng` would make the
+        // results of a seed depend on it
+        let tick_rng = match config.seed {
+            Some(seed) => StdRng::seed_from_u64(!seed),
+            None => StdRng::from_os_rng(),
+        };
+
+        let pointer_angles: Vec<f32> = config
+            .pointer_positions
+            .iter()
+            .flatten()
+            .map(|&degrees| deg_to_rad(degrees))
+            .collect();
+        let pointer_angle = pointer_angles
+            .first()
+            .copied()
+            .unwrap_or_else(|| deg_to_rad(config.pointer_angle_deg.unwrap_or(270.0)));
+        let rotation = config
+            .initial_rotation_deg
+            .map(deg_to_rad)
+            .unwrap_or_else(|| rng.random_range(0.0..2.0 * PI));
+
+        let auto_spin_interval = config
+            .auto_spin_interval_ms
+            .filter(|ms| ms.is_finite() && *ms > 0.0)
+            .map(|ms| ms / 1000.0);
+        // The loop starts right away unless a first delay is given
+        let auto_spin_timer = config
+            .auto_spin_after_ms
+            .filter(|ms| ms.is_finite() && *ms >= 0.0)
+            .map(|ms| ms / 1000.0)
+            .or(auto_spin_interval);
+
+        let music_sink = config
+            .music_path
+            .as_ref()
+            .and_then(|path| SoundClip::load(&asset(path)))
+            .and_then(|clip| clip.start_looped(stream_handle.as_ref(), MUSIC_VOLUME));
+
+        let hit_counts: Vec<(String, u32, u32)> = segments
+            .iter()
+            .filter(|s| !s.is_blank)
+            .map(|s| (s.label.clone(), s.weight, 0))
+            .collect();
+
+        let mut app = Self {
+            layout: config
+                .layout
+                .as_deref()
+                .and_then(parse_layout)
+                .unwrap_or(Layout::Wheel),
+            rotation,
+            start_rotation: 0.0,
+            target_rotation: 0.0,
+            current_spin_time: 0.0,
+            spin_elapsed: 0.0,
+            max_spin_seconds: config
+                .max_spin_seconds
+                .filter(|max| max.is_finite() && *max > 0.0),
+            spin_duration_ms: config.spin_duration_ms,
+            scale_duration_with_spins: config.scale_duration_with_spins.unwrap_or(false),
+            reduced_motion,
+            power_spin: config.power_spin.unwrap_or(false),
+            power_charge: None,
+            active_spin_duration_ms: config.spin_duration_ms,
+            easing_bezier: config.easing_bezier.filter(|p| is_valid_bezier(*p)),
+            is_spinning: false,
+            paused: false,
+            snap_winner_to_top: config.snap_winner_to_top.unwrap_or(false),
+            settle_bounce: config.settle_bounce.unwrap_or(false) && !reduced_motion,
+            settle_elapsed: None,
+            windup: config.windup.unwrap_or(false) && !reduced_motion,
+            windup_elapsed: None,
+            reveal_delay: config.reveal_delay_ms.unwrap_or(0.0).max(0.0) / 1000.0,
+            reveal_timer: None,
+            snap_animation: None,
+            auto_spin_timer,
+            auto_spin_interval,
+            auto_spin_paused: false,
+            pointer_angle,
+            pointer_angles,
+            click_to_spin: config.click_to_spin.unwrap_or(true),
+            click_hub_only: config.click_hub_only.unwrap_or(false),
+            anchor_first_segment: config.anchor_first_segment.unwrap_or(false),
+            winner_in_center: config.winner_in_center.unwrap_or(false),
+            spin_mouse_button: config
+                .spin_mouse_button
+                .as_deref()
+                .and_then(parse_mouse_button)
+                .unwrap_or(egui::PointerButton::Primary),
+            drag_spin: config.drag_spin.unwrap_or(false),
+            friction: config
+                .friction
+                .filter(|f| f.is_finite() && *f >= 0.0)
+                .unwrap_or(0.8),
+            drag_angle: None,
+            drag_velocity: 0.0,
+            momentum: None,
+            flick_velocity: 0.0,
+
+            _audio_stream: _stream,
+            audio_handle: stream_handle,
+            last_segment_index: None,
+            tick_mode: config
+                .tick_mode
+                .as_deref()
+                .and_then(parse_tick_mode)
+                .unwrap_or(TickMode::Boundary),
+            boundary_tiebreak: config
+                .boundary_tiebreak
+                .as_deref()
+                .and_then(parse_boundary_tiebreak)
+                .unwrap_or(BoundaryTiebreak::Higher),
+            boundary_choice: None,
+            min_tick_angle: config
+                .ticks_per_revolution
+                .filter(|&ticks| ticks > 0)
+                .map_or(0.0, |ticks| 2.0 * PI / ticks as f32),
+            last_tick_rotation: None,
+            tick_accumulator: 0.0,
+            tension_ticks: config.tension_ticks.unwrap_or(false),
+            tick_sounds: config
+                .tick_sounds
+                .iter()
+                .flatten()
+                .filter_map(|path| SoundClip::load(&asset(path)))
+                .collect(),
+            countdown_cues: config.countdown_cues.unwrap_or(false),
+            next_countdown_cue: auto_spin_timer.map_or(0, |t: f32| t.floor().min(3.0) as u32),
+            countdown_sound: config
+                .countdown_sound_path
+                .as_ref()
+                .and_then(|path| SoundClip::load(&asset(path))),
+            drumroll_sound: config
+                .drumroll_sound_path
+                .as_ref()
+                .and_then(|path| SoundClip::load(&asset(path))),
+            drumroll_sink: None,
+            start_sound: config
+                .start_sound_path
+                .as_ref()
+                .and_then(|path| SoundClip::load(&asset(path))),
+            loop_sound: config
+                .loop_sound_path
+                .as_ref()
+                .and_then(|path| SoundClip::load(&asset(path))),
+            loop_sink: None,
+            music_sink,
+            lock_sound: config
+                .lock_sound_path
+                .as_ref()
+                .and_then(|path| SoundClip::load(&asset(path))),
+            fanfare_sound: config
+                .fanfare_sound_path
+                .as_ref()
+                .and_then(|path| SoundClip::load(&asset(path))),
+            fanfare_delay: config.fanfare_delay_ms.unwrap_or(0.0).max(0.0) / 1000.0,
+
+            frame_interval: config
+                .max_fps
+                .filter(|fps| fps.is_finite() && *fps > 0.0)
+                .map(|fps| 1.0 / fps),
+            max_frame_dt: config
+                .max_frame_dt
+                .filter(|dt| dt.is_finite() && *dt > 0.0)
+                .unwrap_or(0.1),
+            frame_debt: None,
+            background,
+            center_color,
+            center_radius_ratio,
+            center_offset: egui::vec2(
+                config.center_offset_x.unwrap_or(0.0),
+                config.center_offset_y.unwrap_or(0.0),
+            ),
+            winner_templates,
+            winners_per_spin: config.winners_per_spin.unwrap_or(1).max(1),
+            blank_message: config
+                .blank_message
+                .unwrap_or_else(|| "No luck, spin again!".to_string()),
+            active_template: 0,
+            template_notice_timer: 0.0,
+            winner_font_size,
+            winner_card: {
+                let fill = config
+                    .winner_card_color
+                    .as_deref()
+                    .and_then(parse_hex_color);
+                let border = config
+                    .winner_border_color
+                    .as_deref()
+                    .and_then(parse_hex_color);
+                (fill.is_some() || border.is_some()).then(|| WinnerCard {
+                    fill: fill.unwrap_or(egui::Color32::from_black_alpha(200)),
+                    border,
+                    padding: config.winner_card_padding.unwrap_or(16.0).max(0.0),
+                })
+            },
+            winner_position: config
+                .winner_message_position
+                .as_deref()
+                .and_then(parse_winner_position)
+                .unwrap_or(WinnerPosition::Center),
+            label_font_size,
+            label_direction,
+            label_shadow: config.label_shadow.unwrap_or(false),
+            label_bg_color: config.label_bg_color.as_deref().and_then(parse_hex_color),
+            label_number_format: config.label_number_format,
+            label_max_chars: config.label_max_chars.filter(|&max| max > 0),
+            label_wrap_width: config
+                .label_wrap_width
+                .filter(|width| width.is_finite() && *width > 0.0),
+            mystery_mode: config.mystery_mode.unwrap_or(false),
+            max_labels: config.max_labels,
+            show_segments_borders,
+            segment_gap: deg_to_rad(
+                config
+                    .segment_gap_degrees
+                    .filter(|gap| gap.is_finite() && *gap >= 0.0)
+                    .unwrap_or(0.0),
+            ),
+            colorblind_patterns: config.colorblind_patterns.unwrap_or(false),
+            motion_blur: config.motion_blur.unwrap_or(false),
+            angular_velocity: 0.0,
+            show_rim: config.show_rim.unwrap_or(true),
+            rim_color,
+            rim_thickness: config.rim_thickness.unwrap_or(5.0).max(0.0),
+            tilt_scale: deg_to_rad(
+                config
+                    .tilt
+                    .filter(|tilt| tilt.is_finite())
+                    .unwrap_or(0.0)
+                    .clamp(0.0, MAX_TILT_DEGREES),
+            )
+            .cos(),
+            show_legend,
+            odds_format: config
+                .odds_format
+                .as_deref()
+                .and_then(parse_odds_format)
+                .unwrap_or(OddsFormat::Percent),
+            show_slice_odds: config.show_slice_odds.unwrap_or(false),
+            ui_theme: config.ui_theme.as_deref().and_then(parse_theme),
+            high_quality: config.high_quality.unwrap_or(false),
+            high_quality_pending: config.high_quality.unwrap_or(false),
+            ui_scale: config
+                .ui_scale
+                .filter(|s| s.is_finite())
+                .map(|s| s.clamp(MIN_UI_SCALE, MAX_UI_SCALE)),
+            show_remaining_count: config.show_remaining_count.unwrap_or(false),
+            show_ticker: config.show_ticker.unwrap_or(false),
+            show_fairness_info: config.show_fairness_info.unwrap_or(false),
+            ticker_offset: 0.0,
+            zoom_reveal: config.zoom_reveal.unwrap_or(false) && !reduced_motion,
+            confetti: config.confetti.unwrap_or(false) && !reduced_motion,
+            confetti_delay: config.confetti_delay_ms.unwrap_or(0.0).max(0.0) / 1000.0,
+            confetti_pieces: Vec::new(),
+            scheduled: Vec::new(),
+            zoom_timer: 0.0,
+            start_hint,
+            title_text: config.title_text.unwrap_or_default(),
+            title_font_size: config.title_font_size.unwrap_or(32.0),
+            segments,
+            reverse_segment_order: config.reverse_segment_order.unwrap_or(false),
+            segment_sort: config
+                .segment_sort
+                .as_deref()
+                .and_then(parse_segment_sort)
+                .unwrap_or(SegmentSort::Config),
+            total_weight: 0,
+            tag_filters,
+            tag_filter: None,
+            min_slice_fraction: config.min_slice_degrees.unwrap_or(0.0) / 360.0,
+            geometry: WheelGeometry::default(),
+            elimination_mode: config.elimination_mode.unwrap_or(false),
+            eliminate_animation: config.eliminate_animation.unwrap_or(true) && !reduced_motion,
+            removing: Vec::new(),
+            fair_bag: config.fair_bag.unwrap_or(false),
+            rng,
+            tick_rng,
+            seed: config.seed,
+            bag: Vec::new(),
+            winning_label: None,
+            winning_index: None,
+            winning_indices: Vec::new(),
+            spin_count: 0,
+            practice: false,
+            practice_next: false,
+            max_spins_allowed: config.max_spins_allowed,
+            spin_cooldown: config
+                .spin_cooldown_ms
+                .filter(|ms| ms.is_finite() && *ms >= 0.0)
+                .unwrap_or(0.0)
+                / 1000.0,
+            cooldown_remaining: 0.0,
+            cooldown_notice: false,
+            chain_depth: 0,
+            pending_chain: None,
+            history: Vec::new(),
+            show_summary: false,
+            hit_counts,
+            show_stats: false,
+            stats_path: None,
+
+            source_config,
+            result_sender: None,
+            status_file: config.status_file,
+            audit_log: config.audit_log,
+            webhook_url: config.webhook_url,
+            on_win_command: config
+                .on_win_command
+                .filter(|_| config.allow_commands == Some(true)),
+            spin_audit: None,
+            status_elapsed: 0.0,
+            last_status: None,
+        };
+        app.update_layout();
+        app
+    }
+
+    // Rebuilds the slice layout, must be called whenever segments or the filter change
+    fn update_layout(&mut self) {
+        let weights: Vec<u32> = self.segments.iter().map(|s| s.weight).collect();
+        let order: Vec<usize> = self
+            .display_order()
+            .filter(|&i| self.is_active(&self.segments[i]))
+            .collect();
+        self.total_weight = order.iter().map(|&i| weights[i]).sum();
+        self.geometry = WheelGeometry::new(&weights, order, self.min_slice_fraction);
+
+        if self.anchor_first_segment
+            && self.spin_count == 0
+            && let Some(first) = self.geometry.slice(0).or(self.geometry.slices().first())
+        {
+            self.rotation = self.pointer_angle - first.start * 2.0 * PI;
+        }
+    }
+
+    // Whether a segment passes the tag filter, i.e. is on the wheel and can win
+    fn is_active(&self, seg: &ProcessedSegment) -> bool {
+        self.tag_filter
+            .is_none_or(|filter| seg.tags.contains(&self.tag_filters[filter]))
+    }
+
+    fn active_segments(&self) -> impl Iterator<Item = (usize, &ProcessedSegment)> {
+        self.segments
+            .iter()
+            .enumerate()
+            .filter(|(_, seg)| self.is_active(seg))
+    }
+
+    // Steps through no filter, then each tag in turn, then back to no filter
+    fn cycle_tag_filter(&mut self) {
+        self.tag_filter = match self.tag_filter {
+            None if !self.tag_filters.is_empty() => Some(0),
+            Some(i) if i + 1 < self.tag_filters.len() => Some(i + 1),
+            _ => None,
+        };
+        self.bag.clear();
+        self.winning_label = None;
+        self.winning_index = None;
+        self.winning_indices.clear();
+        self.update_layout();
+    }
+
+    /// Sends a [`WinnerResult`] to `sender` every time a spin completes, one per
+    /// winner when `winners_per_spin` is above 1 (the landed segment first).
+    ///
+    /// Results are sent from the UI thread inside `update`, so the receiving
+    /// end should be drained on another thread (or polled without blocking).
+    /// A dropped receiver is ignored.
+    pub fn with_result_channel(mut self, sender: mpsc::Sender<WinnerResult>) -> Self {
+        self.result_sender = Some(sender);
+        self
+    }
+
+    /// Writes the session's [`SpinStats`] to `path` as JSON when the app exits.
+    pub fn with_stats_path(mut self, path: impl Into<String>) -> Self {
+        self.stats_path = Some(path.into());
+        self
+    }
+
+    /// Wins per segment so far, in config order. Blank segments never win and
+    /// are left out.
+    pub fn stats(&self) -> SpinStats {
+        let total_weight: u32 = self.hit_counts.iter().map(|(_, weight, _)| weight).sum();
+        let total_hits: u32 = self.hit_counts.iter().map(|(_, _, hits)| hits).sum();
+        let share = |part: u32, total: u32| {
+            if total == 0 {
+                0.0
+            } else {
+                part as f32 / total as f32
+            }
+        };
+
+        SpinStats {
+            wheel: None,
+            spins: self.spin_count,
+            segments: self
+                .hit_counts
+                .iter()
+                .map(|(label, weight, hits)| SegmentStats {
+                    label: label.clone(),
+                    weight: *weight,
+                    hits: *hits,
+                    expected_share: share(*weight, total_weight),
+                    observed_share: share(*hits, total_hits),
+                })
+                .collect(),
+        }
+    }
+
+    pub(crate) fn write_stats(&self) {
+        if let Some(path) = &self.stats_path {
+            write_stats_json(path, &self.stats());
+        }
+    }
+
+    /// Starts a spin as if Space was pressed. Returns `false` if the wheel is
+    /// already spinning.
+    pub fn spin(&mut self) -> bool {
+        if self.is_spinning || self.spins_exhausted() || self.cooldown_remaining > 0.0 {
+            return false;
+        }
+        self.start_spin();
+        true
+    }
+
+    // A spin started by a chaining segment on another wheel
+    pub(crate) fn spin_chained(&mut self, depth: u32) -> bool {
+        let started = self.spin();
+        if started {
+            self.chain_depth = depth;
+        }
+        started
+    }
+
+    // The wheel to spin and its chain depth, once the chaining winner has been shown
+    pub(crate) fn take_chain_request(&mut self) -> Option<(String, u32)> {
+        if self.pending_chain.as_ref().is_some_and(|(_, r)| *r <= 0.0) {
+            let (name, _) = self.pending_chain.take()?;
+            return Some((name, self.chain_depth + 1));
+        }
+        None
+    }
+
+    pub(crate) fn spins_exhausted(&self) -> bool {
+        self.max_spins_allowed
+            .is_some_and(|max| self.spin_count >= max)
+    }
+
+    fn schedule_auto_spin(&mut self, seconds: f32) {
+        self.auto_spin_timer = Some(seconds);
+        self.next_countdown_cue = seconds.floor().min(3.0) as u32;
+    }
+
+    // Stops or restarts the auto spin loop, a spin already running plays out
+    fn toggle_auto_spin(&mut self) {
+        let Some(interval) = self.auto_spin_interval else {
+            return;
+        };
+        self.auto_spin_paused = !self.auto_spin_paused;
+        if self.auto_spin_paused {
+            self.auto_spin_timer = None;
+        } else if !self.is_spinning {
+            self.schedule_auto_spin(interval);
+        }
+    }
+
+    fn toggle_pause(&mut self) {
+        if !self.is_spinning {
+            return;
+        }
+        self.paused = !self.paused;
+        for sink in self.drumroll_sink.iter().chain(&self.loop_sink) {
+            if self.paused {
+                sink.pause();
+            } else {
+                sink.play();
+            }
+        }
+    }
+
+    // Clears the previous result and animation state shared by every spin model
+    fn reset_spin_state(&mut self) {
+        // Highest index first, removing one doesn't shift the others
+        let mut winners = std::mem::take(&mut self.winning_indices);
+        if self.elimination_mode && !self.practice {
+            winners.sort_unstable_by(|a, b| b.cmp(a));
+            for index in winners {
+                self.eliminate(index);
+            }
+        }
+
+        self.practice = std::mem::take(&mut self.practice_next);
+        self.is_spinning = true;
+        self.paused = false;
+        self.auto_spin_timer = None;
+        if !self.practice {
+            self.spin_count += 1;
+        }
+        self.current_spin_time = 0.0;
+        self.spin_elapsed = 0.0;
+        self.start_rotation = self.rotation;
+        self.winning_label = None;
+        self.winning_index = None;
+        self.zoom_timer = 0.0;
+        self.snap_animation = None;
+        self.settle_elapsed = None;
+        self.windup_elapsed = None;
+        self.reveal_timer = None;
+        self.last_segment_index = None;
+        self.last_tick_rotation = None;
+        self.tick_accumulator = 0.0;
+        self.momentum = None;
+        self.spin_audit = None;
+        self.chain_depth = 0;
+        self.pending_chain = None;
+        self.boundary_choice = None;
+        self.scheduled.clear();
+
+        if let Some(sink) = &self.music_sink {
+            sink.set_volume(MUSIC_DUCKED_VOLUME);
+        }
+        if let Some(clip) = &self.start_sound {
+            clip.play(self.audio_handle.as_ref(), 1.0);
+        }
+        self.loop_sink = self
+            .loop_sound
+            .as_ref()
+            .and_then(|clip| clip.start_looped(self.audio_handle.as_ref(), 1.0));
+    }
+
+    // Takes a segment off the wheel, always leaving at least one
+    fn eliminate(&mut self, index: usize) {
+        if self.segments.len() <= 1 {
+            return;
+        }
+        if self.eliminate_animation
+            && let Some(&bounds) = self.geometry.slice(index)
+        {
+            self.removing.push(RemovingSegment {
+                bounds,
+                color: self.segments[index].color,
+                elapsed: 0.0,
+            });
+        }
+
+        self.segments.remove(index);
+        // Ticket indices refer to the old segment list
+        self.bag.clear();
+        self.update_layout();
+    }
+
+    /// The config the app was started with, updated with the wheel as it is now
+    /// (segments and their colors, as hex).
+    pub fn current_config(&self) -> AppConfig {
+        AppConfig {
+            segments: self
+                .segments
+                .iter()
+                .map(|seg| SegmentConfig {
+                    label: seg.label.clone(),
+                    // The weight of a probability segment is derived, not configured
+                    weight: if seg.probability.is_some() {
+                        0
+                    } else {
+                        seg.weight
+                    },
+                    color: Some(color_to_hex(seg.color)),
+                    font_size: seg.font_size,
+                    is_blank: seg.is_blank.then_some(true),
+                    tags: (!seg.tags.is_empty()).then(|| seg.tags.clone()),
+                    image: seg.image_path.clone(),
+                    chain_to: seg.chain_to.clone(),
+                    probability: seg.probability,
+                })
+                .collect(),
+            ..self.source_config.clone()
+        }
+    }
+
+    fn export_config(&self) {
+        let Some(path) = &self.source_config.export_path else {
+            return;
+        };
+        let result = self
+            .current_config()
+            .to_toml()
+            .and_then(|toml| fs::write(path, toml).map_err(|e| e.to_string()));
+        match result {
+            Ok(()) => println!("saved wheel to '{path}'"),
+            Err(e) => eprintln!("warning: failed to save wheel to '{path}': {e}"),
+        }
+    }
+
+    fn start_spin(&mut self) {
+        let power = self.power_charge.take();
+        if self.spins_exhausted() {
+            self.auto_spin_timer = None;
+            self.show_summary = true;
+            return;
+        }
+        if self.cooldown_remaining > 0.0 {
+            self.cooldown_notice = true;
+            return;
+        }
+        self.reset_spin_state();
+
+        // The landing offset alone still covers every angle, so skipping the
+        // whole turns doesn't change the odds
+        let extra_spins = if self.reduced_motion {
+            0.0
+        } else if let Some(charge) = power {
+            POWER_MIN_TURNS + charge * (POWER_MAX_TURNS - POWER_MIN_TURNS)
+        } else {
+            self.rng
+                .random_range(AVERAGE_SPIN_TURNS - 2.0..AVERAGE_SPIN_TURNS + 2.0)
+        };
+
+        // Drawn widths no longer match the odds with a minimum slice width, so
+        // draw the winner by weight and land somewhere inside its drawn slice
+        let target_index = if self.fair_bag && !self.practice {
+            Some(self.draw_from_bag())
+        } else if self.min_slice_fraction > 0.0 {
+            Some(self.pick_weighted_index())
+        } else {
+            None
+        };
+        let random_offset = match target_index {
+            Some(index) => {
+                let fraction = self.rng.random_range(0.0..1.0);
+                self.rotation_offset_to_segment(index, fraction)
+            }
+            None => self.rng.random_range(0.0..2.0 * PI),
+        };
+        self.target_rotation = self.rotation + extra_spins * 2.0 * PI + random_offset;
+
+        if self.audit_log.is_some() {
+            self.spin_audit = Some(format!(
+                "start_rotation={} extra_spins={extra_spins} random_offset={random_offset} target_rotation={}",
+                self.start_rotation, self.target_rotation
+            ));
+        }
+
+        if self.windup {
+            self.windup_elapsed = Some(0.0);
+        }
+
+        // Keep the perceived speed constant by treating `spin_duration_ms` as the
+        // duration of an average (12 turn) spin
+        self.active_spin_duration_ms = if self.reduced_motion {
+            self.spin_duration_ms.min(REDUCED_MOTION_SPIN_MS)
+        } else if self.scale_duration_with_spins {
+            self.spin_duration_ms * extra_spins / AVERAGE_SPIN_TURNS
+        } else {
+            self.spin_duration_ms
+        };
+    }
+
+    // A spin that runs like any other but doesn't count: nothing is recorded
+    fn start_practice_spin(&mut self) {
+        self.practice_next = true;
+        self.start_spin();
+        self.practice_next = false;
+    }
+
+    // Lets a flicked wheel coast with `velocity` (rad/s) until friction stops it.
+    // The winner is whatever segment it comes to rest on.
+    fn start_drag_spin(&mut self, velocity: f32) {
+        if self.spins_exhausted() {
+            self.show_summary = true;
+            return;
+        }
+        if self.cooldown_remaining > 0.0 {
+            self.cooldown_notice = true;
+            return;
+        }
+        self.reset_spin_state();
+        self.flick_velocity = velocity.clamp(-MAX_FLICK_VELOCITY, MAX_FLICK_VELOCITY);
+        self.momentum = Some(self.flick_velocity);
+
+        if self.audit_log.is_some() {
+            self.spin_audit = Some(format!(
+                "start_rotation={} flick_velocity={} friction={}",
+                self.start_rotation, self.flick_velocity, self.friction
+            ));
+        }
+    }
+
+    // Plays the tick sound for the wheel having moved from `previous_rotation`
+    fn update_ticks(&mut self, previous_rotation: f32) {
+        let (current_index, _, _) = self.get_current_segment_info();
+
+        match self.tick_mode {
+            TickMode::Boundary => {
+                if let Some(last_index) = self.last_segment_index
+                    && last_index != current_index
+                    && self
+                        .last_tick_rotation
+                        .is_none_or(|r| (self.rotation - r).abs() >= self.min_tick_angle)
+                {
+                    self.last_tick_rotation = Some(self.rotation);
+                    self.play_tick_sound();
+                }
+                self.last_segment_index = Some(current_index);
+            }
+            TickMode::Velocity => {
+                // Fixed angular steps make the tick rate follow the wheel's speed
+                self.tick_accumulator += (self.rotation - previous_rotation).abs();
+                if self.tick_accumulator >= VELOCITY_TICK_ANGLE {
+                    self.tick_accumulator %= VELOCITY_TICK_ANGLE;
+                    self.play_tick_sound();
+                }
+            }
+        }
+    }
+
+    // Turns the wheel along with a pointer dragged around the hub and flicks it
+    // on release. Returns early when the button isn't held on the wheel.
+    fn handle_drag(&mut self, ctx: &egui::Context, center: egui::Pos2, radius: f32, dt: f32) {
+        let button = self.spin_mouse_button;
+        let (pressed, down, pos) = ctx.input(|i| {
+            (
+                i.pointer.button_pressed(button),
+                i.pointer.button_down(button),
+                i.pointer.interact_pos(),
+            )
+        });
+        let angle_at = |pos: egui::Pos2| (self.untilt(center, pos) - center).angle();
+
+        if !down {
+            if self.drag_angle.take().is_some() && self.drag_velocity.abs() >= MIN_FLICK_VELOCITY {
+                self.start_drag_spin(self.drag_velocity);
+            }
+            return;
+        }
+
+        let Some(pos) = pos else {
+            return;
+        };
+
+        if pressed && !self.is_spinning && self.untilt(center, pos).distance(center) <= radius {
+            self.drag_angle = Some(angle_at(pos));
+            self.drag_velocity = 0.0;
+            self.snap_animation = None;
+            return;
+        }
+
+        if let Some(last_angle) = self.drag_angle {
+            let angle = angle_at(pos);
+            let mut delta = (angle - last_angle).rem_euclid(2.0 * PI);
+            if delta > PI {
+                delta -= 2.0 * PI;
+            }
+
+            self.rotation += delta;
+            self.drag_angle = Some(angle);
+            if dt > 0.0 {
+                // Smoothed so a single jittery frame before release doesn't decide the flick
+                self.drag_velocity += (delta / dt - self.drag_velocity) * 0.5;
+            }
+            self.request_animation_frame(ctx);
+        }
+    }
+
+    // The landed segment followed by `winners_per_spin - 1` further non-blank
+    // segments drawn by weight without replacement
+    fn draw_extra_winners(&mut self, landed: usize) -> Vec<usize> {
+        let mut winners = vec![landed];
+
+        while winners.len() < self.winners_per_spin {
+            let remaining: u32 = self
+                .active_segments()
+                .filter(|(i, s)| !s.is_blank && !winners.contains(i))
+                .map(|(_, s)| s.weight)
+                .sum();
+            if remaining == 0 {
+                break;
+            }
+
+            let mut roll = self.rng.random_range(0..remaining);
+            for (i, seg) in self.active_segments() {
+                if seg.is_blank || winners.contains(&i) {
+                    continue;
+                }
+                if roll < seg.weight {
+                    winners.push(i);
+                    break;
+                }
+                roll -= seg.weight;
+            }
+        }
+
+        winners
+    }
+
+    fn draw_from_bag(&mut self) -> usize {
+        if self.bag.is_empty() {
+            self.bag = self
+                .active_segments()
+                .flat_map(|(i, seg)| std::iter::repeat_n(i, seg.weight as usize))
+                .collect();
+            self.bag.shuffle(&mut self.rng);
+        }
+        self.bag.pop().unwrap_or(0)
+    }
+
+    fn pick_weighted_index(&mut self) -> usize {
+        let mut roll = self.rng.random_range(0..self.total_weight.max(1));
+        let mut last = 0;
+        for (i, seg) in self.active_segments() {
+            if roll < seg.weight {
+                return i;
+            }
+            roll -= seg.weight;
+            last = i;
+        }
+        last
+    }
+
+    // Forward rotation (0..2π) after which the pointer sits at `fraction` of the
+    // way through the given segment
+    fn rotation_offset_to_segment(&self, index: usize, fraction: f32) -> f32 {
+        let Some(slice) = self.geometry.slice(index) else {
+            return 0.0;
+        };
+        let hit_angle = slice.start_angle(0.0) + slice.width() * fraction;
+        (self.pointer_angle - hit_angle - self.rotation).rem_euclid(2.0 * PI)
+    }
+
+    // Turns the wheel so the pointer rests on the middle of the next (`step` = 1)
+    // or previous (-1) slice, as an animated preview between spins
+    fn preview_step(&mut self, step: isize) {
+        let slices = self.geometry.slices();
+        let base = self.snap_animation.as_ref().map_or(self.rotation, |s| s.to);
+        let Some(current) = self
+            .geometry
+            .segment_at(base, self.pointer_angle)
+            .and_then(|index| slices.iter().position(|s| s.index == index))
+        else {
+            return;
+        };
+
+        let next = &slices[(current as isize + step).rem_euclid(slices.len() as isize) as usize];
+        let mut delta = (self.pointer_angle - next.mid_angle(base)).rem_euclid(2.0 * PI);
+        if delta > PI {
+            delta -= 2.0 * PI;
+        }
+        self.snap_animation = Some(SnapAnimation {
+            from: self.rotation,
+            to: base + delta,
+            elapsed: 0.0,
+        });
+    }
+
+    // Called once the wheel has come to rest, holds it there for the reveal delay
+    fn begin_reveal(&mut self) {
+        self.settle_elapsed = None;
+
+        if self.reveal_delay > 0.0 {
+            self.reveal_timer = Some(self.reveal_delay);
+            self.drumroll_sink = self
+                .drumroll_sound
+                .as_ref()
+                .and_then(|clip| clip.start(self.audio_handle.as_ref(), 1.0));
+        } else {
+            self.finish_spin();
+        }
+    }
+
+    // Ends the spin on the spot, the segment under the pointer winning. A spin
+    // already waiting on its reveal is at rest, so only the wait is skipped.
+    fn force_stop(&mut self) {
+        if self.reveal_timer.is_none() {
+            self.target_rotation = self.rotation;
+            self.play_lock_sound();
+        }
+        self.momentum = None;
+        self.windup_elapsed = None;
+        self.finish_spin();
+    }
+
+    // Latches the segment under the pointer as the winner and starts the reveal effects
+    fn finish_spin(&mut self) {
+        self.is_spinning = false;
+        self.paused = false;
+        self.cooldown_remaining = self.spin_cooldown;
+        self.cooldown_notice = false;
+        self.settle_elapsed = None;
+        self.reveal_timer = None;
+        for sink in self
+            .drumroll_sink
+            .take()
+            .into_iter()
+            .chain(self.loop_sink.take())
+        {
+            sink.stop();
+        }
+        if let Some(sink) = &self.music_sink {
+            sink.set_volume(MUSIC_VOLUME);
+        }
+
+        // The winner is decided by the intended resting angle, whatever the
+        // last frame's timing made of the animation
+        self.rotation = self.target_rotation;
+        if self.boundary_tiebreak == BoundaryTiebreak::Random
+            && let Some((lower, higher)) =
+                self.geometry.boundary_at(self.rotation, self.pointer_angle)
+        {
+            self.boundary_choice = Some(if self.rng.random_bool(0.5) {
+                lower
+            } else {
+                higher
+            });
+        }
+        let (current_index, _, _) = self.get_current_segment_info();
+        // Landing on a blank slice ends the spin without any winners, with
+        // several pointers that goes for each of them
+        let winners = if !self.pointer_angles.is_empty() {
+            let mut winners: Vec<usize> = Vec::new();
+            for &angle in &self.pointer_angles {
+                let index = self.segment_at_angle(angle);
+                if !self.segments[index].is_blank && !winners.contains(&index) {
+                    winners.push(index);
+                }
+            }
+            winners
+        } else if self.segments[current_index].is_blank {
+            Vec::new()
+        } else {
+            self.draw_extra_winners(current_index)
+        };
+
+        let results: Vec<WinnerResult> = winners
+            .iter()
+            .map(|&index| WinnerResult {
+                label: self.segments[index].label.clone(),
+                index,
+                weight: self.segments[index].weight,
+            })
+            .collect();
+
+        let labels: Vec<String> = winners
+            .iter()
+            .map(|&i| self.display_label(&self.segments[i].label).into_owned())
+            .collect();
+        if !self.practice {
+            self.record_results(current_index, results, &labels);
+        }
+        self.winning_label = Some(labels.join("\n"));
+        // The main pointer may rest on a blank while the others found winners
+        self.winning_index = Some(winners.first().copied().unwrap_or(current_index));
+        self.winning_indices = winners.clone();
+        if self.spins_exhausted() {
+            self.show_summary = true;
+        } else if let Some(interval) = self.auto_spin_interval
+            && !self.auto_spin_paused
+        {
+            // Practice spins included, they interrupt the loop like any spin
+            self.schedule_auto_spin(interval);
+        }
+
+        if self.zoom_reveal {
+            self.zoom_timer = ZOOM_REVEAL_SECONDS;
+        }
+
+        if !winners.is_empty() {
+            if self.confetti {
+                self.schedule_reveal_effect(self.confetti_delay, RevealEffect::Confetti);
+            }
+            if self.fanfare_sound.is_some() {
+                self.schedule_reveal_effect(self.fanfare_delay, RevealEffect::Fanfare);
+            }
+        }
+
+        if self.snap_winner_to_top {
+            // Shortest signed turn that puts the slice center on the pointer.
+            // The pointer already lies inside the slice, so the winner is unchanged.
+            let mut delta =
+                (self.pointer_angle - self.segment_mid_angle(current_index)).rem_euclid(2.0 * PI);
+            if delta > PI {
+                delta -= 2.0 * PI;
+            }
+            self.snap_animation = Some(SnapAnimation {
+                from: self.rotation,
+                to: self.rotation + delta,
+                elapsed: 0.0,
+            });
+        }
+    }
+
+    // Everything that keeps a trace of a result: stats, history, the result
+    // channel, outputs and chaining. Practice spins skip it.
+    fn record_results(&mut self, landed: usize, results: Vec<WinnerResult>, labels: &[String]) {
+        for result in &results {
+            if let Some(entry) = self
+                .hit_counts
+                .iter_mut()
+                .find(|(l, ..)| *l == result.label)
+            {
+                entry.2 += 1;
+            }
+        }
+
+        if let Some(sender) = &self.result_sender {
+            for result in &results {
+                let _ = sender.send(result.clone());
+            }
+        }
+
+        if let Some(command) = &self.on_win_command
+            && !results.is_empty()
+        {
+            let winners: Vec<&str> = results.iter().map(|r| r.label.as_str()).collect();
+            crate::command::run_on_win(command, &winners);
+        }
+
+        #[cfg(feature = "webhook")]
+        if let Some(url) = &self.webhook_url
+            && !results.is_empty()
+        {
+            crate::webhook::post_results(url, self.spin_count, results);
+        }
+
+        self.write_audit_line(landed);
+
+        self.history.extend_from_slice(labels);
+        if let Some(target) = &self.segments[landed].chain_to
+            && !labels.is_empty()
+        {
+            if self.chain_depth < MAX_CHAIN_DEPTH {
+                self.pending_chain = Some((target.clone(), CHAIN_DELAY_SECONDS));
+            } else {
+                eprintln!("warning: stopped chaining to '{target}' after {MAX_CHAIN_DEPTH} spins");
+            }
+        }
+    }
+
+    // Appends the spin's random inputs and the landed segment to `audit_log`,
+    // enough to recompute the result from the same inputs
+    fn write_audit_line(&mut self, landed: usize) {
+        let (Some(path), Some(inputs)) = (&self.audit_log, self.spin_audit.take()) else {
+            return;
+        };
+        let seed = self
+            .seed
+            .map_or_else(|| "none".to_string(), |seed| seed.to_string());
+        let line = format!(
+            "spin {}: seed={seed} {inputs} final_rotation={} winner={:?} index={landed}\n",
+            self.spin_count, self.rotation, self.segments[landed].label
+        );
+
+        if path == "-" {
+            eprint!("{line}");
+            return;
+        }
+        let result = fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)
+            .and_then(|mut file| file.write_all(line.as_bytes()));
+        if let Err(e) = result {
+            eprintln!("warning: failed to write audit log '{path}': {e}");
+        }
+    }
+
+    // Mirrors the segment under the pointer to `status_file` for external overlays.
+    // Throttled while spinning, written once when the wheel comes to rest.
+    fn write_status(&mut self, dt: f32) {
+        let Some(path) = &self.status_file else {
+            return;
+        };
+
+        self.status_elapsed += dt;
+        if self.is_spinning && self.status_elapsed < STATUS_WRITE_INTERVAL {
+            return;
+        }
+
+        let (_, label, _) = self.get_current_segment_info();
+        if self.last_status.as_deref() == Some(label) {
+            return;
+        }
+
+        if let Err(e) = fs::write(path, label) {
+            eprintln!("warning: failed to write status file '{path}': {e}");
+        }
+        self.last_status = Some(label.to_string());
+        self.status_elapsed = 0.0;
+    }
+
+    // Asks for the next frame of an ongoing animation. Animations advance by the
+    // measured frame time, so a capped frame rate only drops frames.
+    fn request_animation_frame(&self, ctx: &egui::Context) {
+        match self.frame_interval {
+            Some(interval) => ctx.request_repaint_after_secs(interval),
+            None => ctx.request_repaint(),
+        }
+    }
+
+    // How far the current spin is towards coming to rest, 0..=1
+    fn spin_progress(&self) -> f32 {
+        match self.momentum {
+            Some(velocity) if self.flick_velocity != 0.0 => {
+                1.0 - velocity.abs() / self.flick_velocity.abs()
+            }
+            Some(_) => 1.0,
+            None => (self.current_spin_time / (self.active_spin_duration_ms / 1000.0)).min(1.0),
+        }
+    }
+
+    /// Advances every timer and animation by `dt` seconds: the spin itself,
+    /// tick sounds, the reveal and the post-spin effects. Returns whether
+    /// anything is still animating and needs another frame.
+    ///
+    /// This is all of the app's time-dependent logic, so driving it with a
+    /// fixed `dt` sequence after [`Self::spin`] plays a spin out without a
+    /// window. The winner arrives on the result channel.
+    pub fn step(&mut self, dt: f32) -> bool {
+        let mut animating = false;
+        let rotation_before = self.rotation;
+
+        if self.template_notice_timer > 0.0 {
+            self.template_notice_timer -= dt;
+            animating = true;
+        }
+
+        if !self.removing.is_empty() {
+            self.removing.retain_mut(|r| {
+                r.elapsed += dt;
+                r.elapsed < ELIMINATE_SECONDS
+            });
+            animating = true;
+        }
+
+        if self.zoom_timer > 0.0 {
+            self.zoom_timer = (self.zoom_timer - dt).max(0.0);
+            animating = true;
+        }
+
+        if self.cooldown_remaining > 0.0 {
+            self.cooldown_remaining = (self.cooldown_remaining - dt).max(0.0);
+            animating = true;
+        }
+
+        // Effects due by the end of this frame fire now, the rest count down
+        if !self.scheduled.is_empty() {
+            let mut due = Vec::new();
+            self.scheduled.retain_mut(|(remaining, effect)| {
+                *remaining -= dt;
+                if *remaining <= 0.0 {
+                    due.push(*effect);
+                }
+                *remaining > 0.0
+            });
+            for effect in due {
+                self.fire_reveal_effect(effect);
+            }
+            animating = true;
+        }
+
+        if !self.confetti_pieces.is_empty() {
+            self.confetti_pieces.retain_mut(|piece| {
+                piece.velocity.y += CONFETTI_GRAVITY * dt;
+                piece.pos += piece.velocity * dt;
+                piece.angle += piece.spin * dt;
+                piece.elapsed += dt;
+                piece.elapsed < CONFETTI_SECONDS
+            });
+            animating = true;
+        }
+
+        if let Some((_, remaining)) = &mut self.pending_chain
+            && *remaining > 0.0
+        {
+            *remaining -= dt;
+            animating = true;
+        }
+
+        if let Some(charge) = &mut self.power_charge {
+            *charge = (*charge + dt / POWER_CHARGE_SECONDS).min(1.0);
+            animating = true;
+        }
+
+        if self.show_ticker && !self.is_spinning {
+            self.ticker_offset += TICKER_SPEED * dt;
+            animating = true;
+        }
+
+        if let Some(remaining) = self.auto_spin_timer
+            && !self.is_spinning
+        {
+            let next = remaining - dt;
+
+            // One cue per second over the last three seconds, each played once
+            if self.countdown_cues
+                && self.next_countdown_cue > 0
+                && next <= self.next_countdown_cue as f32
+            {
+                self.play_countdown_cue(self.next_countdown_cue);
+                self.next_countdown_cue -= 1;
+            }
+
+            if next <= 0.0 {
+                self.start_spin();
+            } else {
+                self.auto_spin_timer = Some(next);
+                animating = true;
+            }
+        }
+
+        if self.is_spinning && !self.paused {
+            self.spin_elapsed += dt;
+            if let Some(max) = self.max_spin_seconds
+                && self.spin_elapsed >= max
+            {
+                eprintln!("warning: spin stopped after max_spin_seconds ({max}s)");
+                self.force_stop();
+            } else if let Some(remaining) = self.reveal_timer {
+                // The wheel is at rest, only the reveal is pending
+                let remaining = remaining - dt;
+                if remaining <= 0.0 {
+                    self.finish_spin();
+                } else {
+                    self.reveal_timer = Some(remaining);
+                }
+            } else if let Some(velocity) = self.momentum {
+                let previous_rotation = self.rotation;
+                let deceleration = (self.friction * velocity.abs() + BASE_DECELERATION) * dt;
+
+                if deceleration >= velocity.abs() {
+                    // Come to rest, then settle and reveal like a timed spin
+                    self.momentum = None;
+                    self.target_rotation = self.rotation;
+                    self.play_lock_sound();
+                    if self.settle_bounce {
+                        self.settle_elapsed = Some(0.0);
+                    } else {
+                        self.begin_reveal();
+                    }
+                } else {
+                    self.rotation += velocity * dt;
+                    self.momentum = Some(velocity - deceleration * velocity.signum());
+                    self.update_ticks(previous_rotation);
+                }
+            } else if let Some(elapsed) = self.settle_elapsed {
+                // Damped wobble around the resting angle, back to exactly zero at the end
+                let elapsed = elapsed + dt;
+                let s = (elapsed / SETTLE_SECONDS).min(1.0);
+                let wobble = SETTLE_AMPLITUDE * (1.0 - s).powi(2) * (s * 3.0 * PI).sin();
+
+                self.rotation = self.target_rotation + wobble;
+                self.settle_elapsed = Some(elapsed);
+
+                if s >= 1.0 {
+                    self.begin_reveal();
+                }
+            } else if let Some(elapsed) = self.windup_elapsed {
+                // Back against the spin direction and returning to the start,
+                // the spin proper then runs from there as usual
+                let elapsed = elapsed + dt;
+                let s = (elapsed / WINDUP_SECONDS).min(1.0);
+                self.rotation = self.start_rotation - WINDUP_ANGLE * (s * PI).sin();
+                self.windup_elapsed = (s < 1.0).then_some(elapsed);
+            } else {
+                self.current_spin_time += dt;
+                let duration = self.active_spin_duration_ms / 1000.0;
+                let t = (self.current_spin_time / duration).clamp(0.0, 1.0);
+
+                let eased = match self.easing_bezier {
+                    Some(points) => cubic_bezier(points, t),
+                    None => 1.0 - (1.0 - t).powi(5),
+                };
+                let previous_rotation = self.rotation;
+
+                self.rotation =
+                    self.start_rotation + eased * (self.target_rotation - self.start_rotation);
+
+                self.update_ticks(previous_rotation);
+
+                if t >= 1.0 {
+                    self.rotation = self.target_rotation;
+                    self.play_lock_sound();
+                    if self.settle_bounce {
+                        self.settle_elapsed = Some(0.0);
+                    } else {
+                        self.begin_reveal();
+                    }
+                }
+            }
+
+            animating = true;
+        }
+
+        if let Some(snap) = &mut self.snap_animation {
+            snap.elapsed += dt;
+            let t = (snap.elapsed / SNAP_SECONDS).min(1.0);
+            let eased = 1.0 - (1.0 - t).powi(3);
+
+            self.rotation = snap.from + (snap.to - snap.from) * eased;
+            if t >= 1.0 {
+                self.snap_animation = None;
+            }
+            animating = true;
+        }
+
+        self.angular_velocity = if dt > 0.0 {
+            (self.rotation - rotation_before) / dt
+        } else {
+            0.0
+        };
+
+        self.write_status(dt);
+
+        animating
+    }
+
+    // Steps by one measured frame time, clamped to `max_frame_dt`. A stall is
+    // spread over the next frames instead of being dropped, so the animation
+    // keeps to wall time without jumping.
+    fn advance(&mut self, frame_dt: f32) -> bool {
+        let elapsed = match self.frame_debt {
+            Some(debt) => frame_dt + debt,
+            None => frame_dt.min(self.max_frame_dt),
+        };
+        let dt = elapsed.min(self.max_frame_dt);
+
+        let animating = self.step(dt);
+        self.frame_debt = animating.then_some(elapsed - dt);
+        animating
+    }
+
+    fn schedule_reveal_effect(&mut self, delay: f32, effect: RevealEffect) {
+        if delay > 0.0 {
+            self.scheduled.push((delay, effect));
+        } else {
+            self.fire_reveal_effect(effect);
+        }
+    }
+
+    fn fire_reveal_effect(&mut self, effect: RevealEffect) {
+        match effect {
+            RevealEffect::Confetti => {
+                let mut rng = rand::rng();
+                let colors: Vec<egui::Color32> = self.segments.iter().map(|s| s.color).collect();
+                self.confetti_pieces
+                    .extend((0..CONFETTI_COUNT).map(|_| ConfettiPiece {
+                        pos: egui::vec2(rng.random_range(-0.2..0.2), -0.9),
+                        velocity: egui::vec2(
+                            rng.random_range(-0.9..0.9),
+                            rng.random_range(-1.6..-0.6),
+                        ),
+                        color: colors[rng.random_range(0..colors.len())],
+                        angle: rng.random_range(0.0..PI),
+                        spin: rng.random_range(-8.0..8.0),
+                        elapsed: 0.0,
+                    }));
+            }
+            RevealEffect::Fanfare => {
+                if let Some(clip) = &self.fanfare_sound {
+                    clip.play(self.audio_handle.as_ref(), 1.0);
+                }
+            }
+        }
+    }
+
+    // Small spinning rectangles, fading out over the last part of their life
+    fn draw_confetti(&self, painter: &egui::Painter, center: egui::Pos2, radius: f32) {
+        for piece in &self.confetti_pieces {
+            let fade = ((CONFETTI_SECONDS - piece.elapsed) / (CONFETTI_SECONDS * 0.3)).min(1.0);
+            let pos = center + piece.pos * radius;
+            let (sin, cos) = piece.angle.sin_cos();
+            let corners = [(-5.0, -2.5), (5.0, -2.5), (5.0, 2.5), (-5.0, 2.5)]
+                .map(|(x, y)| pos + egui::vec2(x * cos - y * sin, x * sin + y * cos));
+            painter.add(egui::Shape::convex_polygon(
+                corners.to_vec(),
+                piece.color.gamma_multiply(fade),
+                egui::Stroke::NONE,
+            ));
+        }
+    }
+
+    fn play_tick_sound(&mut self) {
+        let pitch_jitter = self.tick_rng.random_range(550.0..650.0);
+        let mut volume_jitter = self.tick_rng.random_range(0.0005..0.0015);
+
+        // The last few ticks before the wheel stops are the loudest
+        if self.tension_ticks {
+            volume_jitter *= 1.0 + TENSION_GAIN * self.spin_progress().powi(3);
+        }
+
+        match self.tick_sounds.as_slice() {
+            [] => play_tone(self.audio_handle.as_ref(), pitch_jitter, 30, volume_jitter),
+            // The same jitter, relative to the synthesized tick's center values
+            pool => pool[self.tick_rng.random_range(0..pool.len())].play_at_speed(
+                self.audio_handle.as_ref(),
+                TICK_SAMPLE_VOLUME * volume_jitter / 0.001,
+                pitch_jitter / 600.0,
+            ),
+        }
+    }
+
+    // `remaining` is the whole number of seconds left before the auto-spin (3, 2 or 1)
+    fn play_countdown_cue(&self, remaining: u32) {
+        match &self.countdown_sound {
+            Some(clip) => clip.play(self.audio_handle.as_ref(), 1.0),
+            None => play_tone(
+                self.audio_handle.as_ref(),
+                440.0 + 220.0 * remaining as f32,
+                120,
+                0.004,
+            ),
+        }
+    }
+
+    // Louder and lower than a tick, so the stop stands out from the last ticks.
+    // The wheel is at rest from here on, so the motion loop ends too.
+    fn play_lock_sound(&mut self) {
+        if let Some(sink) = self.loop_sink.take() {
+            sink.stop();
+        }
+        match &self.lock_sound {
+            Some(clip) => clip.play(self.audio_handle.as_ref(), 1.0),
+            None => play_tone(self.audio_handle.as_ref(), 300.0, 45, 0.003),
+        }
+    }
+
+    fn winner_message(&self) -> Option<String> {
+        let winner = self.winning_label.as_ref()?;
+        if self
+            .winning_index
+            .is_some_and(|i| self.segments[i].is_blank)
+        {
+            return Some(self.blank_message.clone());
+        }
+        Some(self.winner_templates[self.active_template].replace("{label}", winner))
+    }
+
+    // The wheel is only painted, so describe it to assistive tech as a button
+    // that spins it, and keep a polite live region that announces each winner
+    fn update_accessibility(&mut self, ctx: &egui::Context, ui: &egui::Ui, wheel_rect: egui::Rect) {
+        let wheel_id = ui.id().with("wheel");
+        let response = ui.interact(wheel_rect, wheel_id, egui::Sense::hover());
+        response.widget_info(|| {
+            egui::WidgetInfo::labeled(
+                egui::WidgetType::Button,
+                !self.is_spinning,
+                "Spin the wheel",
+            )
+        });
+        ctx.accesskit_node_builder(wheel_id, |node| node.add_action(accesskit::Action::Click));
+
+        if ctx.input(|i| i.has_accesskit_action_request(wheel_id, accesskit::Action::Click))
+            && !self.is_spinning
+        {
+            self.start_spin();
+        }
+
+        let announcement = self.winner_message().filter(|_| !self.is_spinning);
+        ctx.accesskit_node_builder(ui.id().with("winner_announcement"), |node| {
+            node.set_role(accesskit::Role::Status);
+            node.set_live(accesskit::Live::Polite);
+            if let Some(message) = announcement {
+                node.set_value(message);
+            }
+        });
+    }
+
+    // A label as shown on screen, with numbers formatted per `label_number_format`
+    fn display_label<'a>(&self, label: &'a str) -> Cow<'a, str> {
+        self.label_number_format
+            .as_deref()
+            .and_then(|format| format_number_label(label, format))
+            .map_or(Cow::Borrowed(label), Cow::Owned)
+    }
+
+    // Text drawn on a slice (and in the legend), hidden in mystery mode except
+    // for the winner once it has been revealed
+    fn slice_text(&self, index: usize) -> Cow<'_, str> {
+        let revealed = !self.is_spinning && self.winning_index == Some(index);
+        if self.mystery_mode && !revealed {
+            Cow::Borrowed("?")
+        } else {
+            self.display_label(&self.segments[index].label)
+        }
+    }
+
+    fn get_current_segment_info(&self) -> (usize, &str, egui::Color32) {
+        let index = self.segment_at_angle(self.pointer_angle);
+        let seg = &self.segments[index];
+        (index, &seg.label, seg.color)
+    }
+
+    // The segment a pointer at screen `angle` points into, boundaries resolved
+    // by `boundary_tiebreak`. A random pick is made once per spin, for the main pointer.
+    fn segment_at_angle(&self, angle: f32) -> usize {
+        match self.geometry.boundary_at(self.rotation, angle) {
+            Some((lower, higher)) => match self.boundary_tiebreak {
+                BoundaryTiebreak::Lower => lower,
+                BoundaryTiebreak::Higher => higher,
+                BoundaryTiebreak::Random => self
+                    .boundary_choice
+                    .filter(|&choice| choice == lower || choice == higher)
+                    .unwrap_or(higher),
+            },
+            None => self.geometry.segment_at(self.rotation, angle).unwrap_or(0),
+        }
+    }
+
+    // Screen angle of the middle of a segment at the current rotation
+    fn segment_mid_angle(&self, index: usize) -> f32 {
+        self.geometry
+            .slice(index)
+            .map_or(self.rotation, |s| s.mid_angle(self.rotation))
+    }
+
+    // Segment indices in the order they are laid out clockwise from `rotation`.
+    // The sorts are stable, so equal segments keep their config order.
+    fn display_order(&self) -> impl Iterator<Item = usize> + use<> {
+        let mut order: Vec<usize> = (0..self.segments.len()).collect();
+        let segments = &self.segments;
+        match self.segment_sort {
+            SegmentSort::Config => {}
+            SegmentSort::Label => {
+                order.sort_by_cached_key(|&i| segments[i].label.to_lowercase());
+            }
+            SegmentSort::WeightAsc => order.sort_by_key(|&i| segments[i].weight),
+            SegmentSort::WeightDesc => {
+                order.sort_by_key(|&i| std::cmp::Reverse(segments[i].weight));
+            }
+        }
+        if self.reverse_segment_order {
+            order.reverse();
+        }
+        order.into_iter()
+    }
+
+    fn layout_label(
+        &self,
+        painter: &egui::Painter,
+        text: &str,
+        font_size: f32,
+        color: egui::Color32,
+    ) -> std::sync::Arc<egui::Galley> {
+        // Wrapped lines are centered under each other, not ragged
+        let (text, halign) = match self.label_direction {
+            LabelDirection::Ltr if self.label_wrap_width.is_some() => {
+                (text.to_string(), egui::Align::Center)
+            }
+            LabelDirection::Ltr => (text.to_string(), egui::Align::LEFT),
+            LabelDirection::Rtl => (text.to_string(), egui::Align::RIGHT),
+            LabelDirection::Vertical => (
+                text.chars()
+                    .filter(|c| !c.is_control())
+                    .map(String::from)
+                    .collect::<Vec<_>>()
+                    .join("\n"),
+                egui::Align::Center,
+            ),
+        };
+
+        let wrap_width = match self.label_direction {
+            LabelDirection::Vertical => f32::INFINITY,
+            _ => self.label_wrap_width.unwrap_or(f32::INFINITY),
+        };
+        let mut job = egui::text::LayoutJob::simple(
+            text,
+            egui::FontId::proportional(font_size),
+            color,
+            wrap_width,
+        );
+        job.halign = halign;
+        painter.layout_job(job)
+    }
+
+    // Draws a segment label centered on `pos` in the configured label style
+    fn draw_label(&self, painter: &egui::Painter, index: usize, font_size: f32, pos: egui::Pos2) {
+        let seg = &self.segments[index];
+        // Contrast against the label chip when it mostly hides the slice
+        let backdrop = match self.label_bg_color {
+            Some(bg) if bg.a() >= 128 => bg,
+            _ => seg.color,
+        };
+        let text_color = if is_bright(backdrop) {
+            egui::Color32::BLACK
+        } else {
+            egui::Color32::WHITE
+        };
+        let text = self.slice_text(index);
+        let mut text = match self.label_max_chars {
+            Some(max) => truncate_with_ellipsis(&text, max),
+            None => text,
+        };
+        // The odds go on their own line, never cut off with the label
+        if self.show_slice_odds {
+            let odds = format_odds(seg.weight, self.total_weight, self.odds_format);
+            text = Cow::Owned(format!("{text}\n{odds}"));
+        }
+        let galley = self.layout_label(painter, &text, font_size, text_color);
+        let text_rect = egui::Align2::CENTER_CENTER.anchor_size(pos, galley.size());
+
+        // Right/center aligned galleys extend to the left of their origin
+        let text_origin = text_rect.min - galley.rect.min.to_vec2();
+
+        if let Some(bg_color) = self.label_bg_color {
+            painter.rect_filled(text_rect.expand(3.0), 4.0, bg_color);
+        }
+
+        if self.label_shadow {
+            painter.galley_with_override_text_color(
+                text_origin + egui::vec2(2.0, 2.0),
+                galley.clone(),
+                egui::Color32::from_black_alpha(140),
+            );
+        }
+
+        painter.galley(text_origin, galley, text_color);
+    }
+
+    // Slot machine layout: the segments as rows of a looping vertical strip
+    // scrolling past a center line, which plays the part of the pointer
+    fn draw_reel(&self, painter: &egui::Painter, strip: egui::Rect, labels_fit: bool) {
+        if self.show_rim {
+            painter.rect_filled(strip.expand(self.rim_thickness), 0.0, self.rim_color);
+        }
+
+        let painter = painter.with_clip_rect(strip);
+        let center_y = strip.center().y;
+        let cycle = (REEL_ROW_HEIGHT * self.geometry.slices().len() as f32).max(strip.height());
+
+        // Strip position (fraction of a pass) currently on the center line
+        let pointer_fraction = ((self.pointer_angle - self.rotation) / (2.0 * PI)).rem_euclid(1.0);
+        let passes = (strip.height() * 0.5 / cycle).ceil() as i32 + 1;
+
+        for pass in -passes..=passes {
+            let top = center_y + (pass as f32 - pointer_fraction) * cycle;
+            for slice in self.geometry.slices() {
+                let seg = &self.segments[slice.index];
+                let row = egui::Rect::from_x_y_ranges(
+                    strip.x_range(),
+                    top + slice.start * cycle..=top + slice.end * cycle,
+                );
+                if !row.intersects(strip) {
+                    continue;
+                }
+
+                painter.rect_filled(row, 0.0, seg.color);
+                if self.show_segments_borders {
+                    painter.hline(
+                        strip.x_range(),
+                        row.top(),
+                        egui::Stroke::new(1.0, egui::Color32::BLACK),
+                    );
+                }
+
+                let font_size = seg.font_size.unwrap_or(self.label_font_size);
+                if font_size > 0.0 && labels_fit && row.height() >= font_size * 0.8 {
+                    self.draw_label(&painter, slice.index, font_size, row.center());
+                }
+            }
+        }
+
+        let (_, _, pointer_color) = self.get_current_segment_info();
+        let stroke = egui::Stroke::new(2.0, egui::Color32::BLACK);
+        painter.hline(
+            strip.x_range(),
+            center_y,
+            egui::Stroke::new(3.0, pointer_color),
+        );
+        for (x, dir) in [(strip.left(), 1.0), (strip.right(), -1.0)] {
+            painter.add(egui::Shape::convex_polygon(
+                vec![
+                    egui::pos2(x, center_y - 12.0),
+                    egui::pos2(x + 18.0 * dir, center_y),
+                    egui::pos2(x, center_y + 12.0),
+                ],
+                pointer_color,
+                stroke,
+            ));
+        }
+    }
+
+    // Name picker layout: the segment under the pointer as one big centered
+    // name on a card of its color, changing as the spin passes each segment
+    fn draw_picker(&self, painter: &egui::Painter, area: egui::Rect) {
+        let (index, _, color) = self.get_current_segment_info();
+        let card = egui::Rect::from_center_size(
+            area.center(),
+            egui::vec2(area.width(), area.height() * 0.4),
+        );
+        if self.show_rim {
+            painter.rect_filled(card.expand(self.rim_thickness), 12.0, self.rim_color);
+        }
+        painter.rect_filled(card, 12.0, color);
+
+        let text_color = if is_bright(color) {
+            egui::Color32::BLACK
+        } else {
+            egui::Color32::WHITE
+        };
+        let text = self.slice_text(index).into_owned();
+        let layout = |font_size: f32| {
+            painter.layout_no_wrap(
+                text.clone(),
+                egui::FontId::proportional(font_size),
+                text_color,
+            )
+        };
+        let mut galley = layout(PICKER_FONT_SIZE);
+        let max_width = card.width() - 32.0;
+        if galley.size().x > max_width {
+            galley = layout(PICKER_FONT_SIZE * max_width / galley.size().x);
+        }
+
+        let pos = egui::Align2::CENTER_CENTER
+            .anchor_size(card.center(), galley.size())
+            .min;
+        painter.galley(pos, galley, text_color);
+    }
+
+    fn draw_legend(&self, ctx: &egui::Context, id: egui::Id, rect: egui::Rect) {
+        egui::Area::new(id)
+            .pivot(egui::Align2::LEFT_TOP)
+            .fixed_pos(rect.left_top() + egui::vec2(10.0, 10.0))
+            .show(ctx, |ui| {
+                egui::Frame::popup(ui.style()).show(ui, |ui| {
+                    egui::Grid::new(id.with("grid"))
+                        .num_columns(3)
+                        .spacing([8.0, 4.0])
+                        .show(ui, |ui| {
+                            for (index, seg) in self.active_segments() {
+                                let (swatch, _) = ui.allocate_exact_size(
+                                    egui::vec2(12.0, 12.0),
+                                    egui::Sense::hover(),
+                                );
+                                ui.painter().rect_filled(swatch, 2.0, seg.color);
+                                ui.label(self.slice_text(index));
+
+                                ui.label(format_odds(
+                                    seg.weight,
+                                    self.total_weight,
+                                    self.odds_format,
+                                ));
+                                ui.end_row();
+                            }
+                        });
+                });
+            });
+    }
+
+    // The winner message, on its card if one is configured. The card goes into
+    // a slot reserved before the text so it ends up behind it.
+    fn winner_label(&self, ui: &mut egui::Ui, text: egui::RichText, text_size: egui::Vec2) {
+        let background = ui.painter().add(egui::Shape::Noop);
+        let response = ui.label(text);
+        if let Some(card) = &self.winner_card {
+            let rect = egui::Rect::from_center_size(response.rect.center(), text_size)
+                .expand(card.padding);
+            ui.painter().set(
+                background,
+                egui::epaint::RectShape::new(
+                    rect,
+                    12.0,
+                    card.fill,
+                    card.border
+                        .map_or(egui::Stroke::NONE, |c| egui::Stroke::new(3.0, c)),
+                    egui::StrokeKind::Outside,
+                ),
+            );
+        }
+    }
+
+    // Where a screen point lies on the untilted wheel, for hit-testing
+    fn untilt(&self, center: egui::Pos2, pos: egui::Pos2) -> egui::Pos2 {
+        egui::pos2(pos.x, center.y + (pos.y - center.y) / self.tilt_scale)
+    }
+
+    // Squashes everything painted on `layer` from shape `first` on vertically
+    // about `center`. The shapes are drawn flat and tessellated here, so the
+    // slices, labels and pointers all follow the same ellipse; the winner is
+    // still read from the flat geometry.
+    fn tilt_shapes(
+        &self,
+        ctx: &egui::Context,
+        layer: egui::LayerId,
+        first: usize,
+        center: egui::Pos2,
+    ) {
+        use egui::layers::ShapeIdx;
+
+        let shapes: Vec<egui::epaint::ClippedShape> = ctx.graphics_mut(|g| {
+            let list = g.entry(layer);
+            (first..list.next_idx().0)
+                .map(|i| {
+                    let mut taken = egui::epaint::ClippedShape {
+                        clip_rect: egui::Rect::EVERYTHING,
+                        shape: egui::Shape::Noop,
+                    };
+                    list.mutate_shape(ShapeIdx(i), |shape| std::mem::swap(shape, &mut taken));
+                    taken
+                })
+                .collect()
+        });
+
+        // Tessellation merges meshes, so there are at most as many primitives
+        // as shapes and the slots left over stay empty
+        let primitives = ctx.tessellate(shapes, ctx.pixels_per_point());
+        ctx.graphics_mut(|g| {
+            let list = g.entry(layer);
+            for (i, primitive) in primitives.into_iter().enumerate() {
+                if let egui::epaint::Primitive::Mesh(mut mesh) = primitive.primitive {
+                    for vertex in &mut mesh.vertices {
+                        vertex.pos.y = center.y + (vertex.pos.y - center.y) * self.tilt_scale;
+                    }
+                    list.set(
+                        ShapeIdx(first + i),
+                        primitive.clip_rect,
+                        egui::Shape::mesh(mesh),
+                    );
+                }
+            }
+        });
+    }
+
+    // Every winner of the session in order, then how often each label won
+    fn draw_summary(&mut self, ctx: &egui::Context, id: egui::Id, rect: egui::Rect) {
+        let mut counts: Vec<(&str, usize)> = Vec::new();
+        for label in &self.history {
+            match counts.iter_mut().find(|(l, _)| l == label) {
+                Some((_, count)) => *count += 1,
+                None => counts.push((label, 1)),
+            }
+        }
+        counts.sort_by_key(|&(_, count)| std::cmp::Reverse(count));
+
+        let mut close = false;
+        egui::Area::new(id)
+            .order(egui::Order::Foreground)
+            .pivot(egui::Align2::CENTER_CENTER)
+            .fixed_pos(rect.center())
+            .show(ctx, |ui| {
+                egui::Frame::popup(ui.style()).show(ui, |ui| {
+                    ui.heading(format!("Summary: {} spins", self.spin_count));
+                    egui::ScrollArea::vertical()
+                        .max_height(rect.height() * 0.6)
+                        .show(ui, |ui| {
+                            if self.history.is_empty() {
+                                ui.label("No winners yet");
+                            }
+                            for (i, label) in self.history.iter().enumerate() {
+                                ui.label(format!("{}. {label}", i + 1));
+                            }
+                            ui.separator();
+                            egui::Grid::new(id.with("counts"))
+                                .num_columns(2)
+                                .spacing([8.0, 4.0])
+                                .show(ui, |ui| {
+                                    for (label, count) in &counts {
+                                        ui.label(*label);
+                                        ui.label(format!("×{count}"));
+                                        ui.end_row();
+                                    }
+                                });
+                        });
+                    close = ui.button("Close").clicked();
+                });
+            });
+
+        if close {
+            self.show_summary = false;
+        }
+    }
+
+    // Wins per segment next to the share its weight predicts
+    fn draw_stats(&mut self, ctx: &egui::Context, id: egui::Id, rect: egui::Rect) {
+        let stats = self.stats();
+        let mut close = false;
+        egui::Area::new(id)
+            .order(egui::Order::Foreground)
+            .pivot(egui::Align2::CENTER_CENTER)
+            .fixed_pos(rect.center())
+            .show(ctx, |ui| {
+                egui::Frame::popup(ui.style()).show(ui, |ui| {
+                    ui.heading(format!("Stats: {} spins", stats.spins));
+                    egui::ScrollArea::vertical()
+                        .max_height(rect.height() * 0.6)
+                        .show(ui, |ui| {
+                            egui::Grid::new(id.with("grid"))
+                                .num_columns(4)
+                                .spacing([12.0, 4.0])
+                                .striped(true)
+                                .show(ui, |ui| {
+                                    ui.strong("Segment");
+                                    ui.strong("Wins");
+                                    ui.strong("Actual");
+                                    ui.strong("Expected");
+                                    ui.end_row();
+                                    for segment in &stats.segments {
+                                        ui.label(self.display_label(&segment.label));
+                                        ui.label(segment.hits.to_string());
+                                        ui.label(format!("{:.1}%", segment.observed_share * 100.0));
+                                        ui.label(format!("{:.1}%", segment.expected_share * 100.0));
+                                        ui.end_row();
+                                    }
+                                });
+                        });
+                    close = ui.button("Close").clicked();
+                });
+            });
+
+        if close {
+            self.show_stats = false;
+        }
+    }
+}
+
+impl OverlayApp {
+    // Input, animation and drawing for one frame, with the wheel filling `ui`
+    pub(crate) fn show(&mut self, ctx: &egui::Context, ui: &mut egui::Ui) {
+        if let Some(theme) = self.ui_theme.take() {
+            ctx.set_theme(theme);
+        }
+        if std::mem::take(&mut self.high_quality_pending) {
+            ctx.tessellation_options_mut(|options| {
+                options.feathering = true;
+                options.feathering_size_in_pixels = 1.0;
+                // Real circles instead of the scaled atlas ones
+                options.prerasterized_discs = false;
+                options.bezier_tolerance = 0.02;
+            });
+        }
+        if let Some(scale) = self.ui_scale.take() {
+            ctx.set_zoom_factor(scale);
+        }
+
+        if ctx.input(|i| i.key_pressed(egui::Key::Escape)) {
+            if self.show_summary {
+                self.show_summary = false;
+            } else if self.show_stats {
+                self.show_stats = false;
+            } else {
+                ctx.send_viewport_cmd(egui::ViewportCommand::Close);
+            }
+        }
+
+        if ctx.input(|i| i.key_pressed(egui::Key::H)) {
+            self.show_summary = !self.show_summary;
+        }
+
+        if ctx.input(|i| i.key_pressed(egui::Key::I)) {
+            self.show_stats = !self.show_stats;
+        }
+
+        if ctx.input(|i| i.key_pressed(egui::Key::Space)) && !self.is_spinning {
+            if ctx.input(|i| i.modifiers.shift) {
+                self.start_practice_spin();
+            } else if self.power_spin {
+                // Held keys repeat, only the first press starts charging
+                self.power_charge.get_or_insert(0.0);
+            } else {
+                self.start_spin();
+            }
+        }
+
+        if self.power_charge.is_some() && ctx.input(|i| i.key_released(egui::Key::Space)) {
+            if self.is_spinning {
+                self.power_charge = None;
+            } else {
+                self.start_spin();
+            }
+        }
+
+        if ctx.input(|i| i.modifiers.command && i.key_pressed(egui::Key::S)) {
+            self.export_config();
+        }
+
+        if !self.is_spinning {
+            if ctx.input(|i| i.key_pressed(egui::Key::ArrowRight)) {
+                self.preview_step(1);
+            }
+            if ctx.input(|i| i.key_pressed(egui::Key::ArrowLeft)) {
+                self.preview_step(-1);
+            }
+        }
+
+        if ctx.input(|i| i.key_pressed(egui::Key::P)) {
+            self.toggle_pause();
+        }
+
+        if ctx.input(|i| i.key_pressed(egui::Key::A)) {
+            self.toggle_auto_spin();
+        }
+
+        if ctx.input(|i| i.key_pressed(egui::Key::L)) {
+            self.show_legend = !self.show_legend;
+        }
+
+        if ctx.input(|i| i.key_pressed(egui::Key::T)) && !self.is_spinning {
+            self.cycle_tag_filter();
+        }
+
+        if ctx.input(|i| i.key_pressed(egui::Key::R)) {
+            self.show_remaining_count = !self.show_remaining_count;
+        }
+
+        if ctx.input(|i| i.key_pressed(egui::Key::W)) && self.winner_templates.len() > 1 {
+            self.active_template = (self.active_template + 1) % self.winner_templates.len();
+            self.template_notice_timer = 1.5;
+        }
+
+        // egui only trusts the measured frame time after an immediate repaint
+        // request, capped frames have to use it directly to keep the timing
+        let frame_dt = ctx.input(|i| {
+            if self.frame_interval.is_some() {
+                i.unstable_dt
+            } else {
+                i.stable_dt
+            }
+        });
+
+        if self.advance(frame_dt) {
+            self.request_animation_frame(ctx);
+        }
+
+        // --- DRAWING ---
+        let mut spin_again = false;
+        let rect = ui.max_rect();
+        let mut center = rect.center() + self.center_offset;
+        // 250 in the default 600x600 window, leaving room for the pointer
+        let mut outer_radius = rect.size().min_elem() * 0.5 - 50.0;
+
+        if let Some(background) = &mut self.background {
+            let uv = background.frame_uv(ctx.input(|i| i.time));
+            if let Some(texture) = background.texture(ctx) {
+                ui.painter()
+                    .image(texture.id(), rect, uv, egui::Color32::WHITE);
+            }
+            if background.is_animated() {
+                ctx.request_repaint_after_secs(background.frame_interval());
+            }
+        }
+
+        if !self.title_text.is_empty() {
+            let title_galley = ui.painter().layout_no_wrap(
+                self.title_text.clone(),
+                egui::FontId::proportional(self.title_font_size),
+                egui::Color32::WHITE,
+            );
+            let title_rect = egui::Align2::CENTER_TOP
+                .anchor_size(egui::pos2(center.x, rect.top() + 6.0), title_galley.size());
+
+            // Make room so the pointer (which reaches 20px past the rim)
+            // stays below the title, keeping the wheel's bottom edge in place
+            let overlap = (title_rect.bottom() + 4.0) - (center.y - outer_radius - 20.0);
+            if overlap > 0.0 {
+                center.y += overlap * 0.5;
+                outer_radius -= overlap * 0.5;
+            }
+
+            ui.painter().rect_filled(
+                title_rect.expand(4.0),
+                4.0,
+                egui::Color32::from_black_alpha(160),
+            );
+            ui.painter()
+                .galley(title_rect.min, title_galley, egui::Color32::WHITE);
+        }
+
+        // Reveal flourish: briefly scale the wheel about a point inside the
+        // winning slice, returning to the normal geometry once the timer ends
+        if self.zoom_timer > 0.0
+            && self.layout == Layout::Wheel
+            && let Some(index) = self.winning_index
+        {
+            let progress = 1.0 - self.zoom_timer / ZOOM_REVEAL_SECONDS;
+            let zoom = 1.0 + ZOOM_REVEAL_SCALE * (progress * PI).sin();
+            let mid = self.segment_mid_angle(index);
+            let focus = center + egui::vec2(mid.cos(), mid.sin()) * outer_radius * 0.6;
+
+            center = focus + (center - focus) * zoom;
+            outer_radius *= zoom;
+        }
+
+        let inner_radius = outer_radius * self.center_radius_ratio;
+
+        let (_, _, pointer_color) = self.get_current_segment_info();
+
+        let click_radius = if self.click_hub_only {
+            inner_radius
+        } else {
+            outer_radius
+        };
+        let wheel_rect =
+            egui::Rect::from_center_size(center, egui::Vec2::splat(outer_radius * 2.0));
+        let hits_wheel = |pos: egui::Pos2| match self.layout {
+            Layout::Wheel => self.untilt(center, pos).distance(center) <= click_radius,
+            Layout::Reel | Layout::Picker => wheel_rect.contains(pos),
+        };
+
+        if self.click_to_spin
+            && let Some(pos) = ctx.input(|i| i.pointer.interact_pos())
+            && hits_wheel(pos)
+            && ctx.input(|i| i.pointer.button_clicked(self.spin_mouse_button))
+            && !self.is_spinning
+        {
+            self.start_spin();
+        }
+
+        if self.drag_spin && self.layout == Layout::Wheel {
+            // The flick speed is measured per frame, a stall's debt isn't part of it
+            let dt = frame_dt.min(self.max_frame_dt);
+            self.handle_drag(ctx, center, outer_radius, dt);
+        }
+
+        self.update_accessibility(ctx, ui, wheel_rect);
+
+        let labels_fit = self
+            .max_labels
+            .is_none_or(|max_labels| self.geometry.slices().len() <= max_labels);
+
+        if self.layout == Layout::Reel {
+            self.draw_reel(ui.painter(), wheel_rect, labels_fit);
+        } else if self.layout == Layout::Picker {
+            self.draw_picker(ui.painter(), wheel_rect);
+        } else {
+            let layer = ui.painter().layer_id();
+            let first_shape = ctx.graphics(|g| g.get(layer).map_or(0, |list| list.next_idx().0));
+
+            if self.show_rim {
+                ui.painter().circle_filled(
+                    center,
+                    outer_radius + self.rim_thickness,
+                    self.rim_color,
+                );
+            }
+
+            let text_r = inner_radius + (outer_radius - inner_radius) * 0.5;
+            let arc_steps = if self.high_quality {
+                (outer_radius / HIGH_QUALITY_CHORD).max(ARC_STEPS_PER_RADIAN)
+            } else {
+                ARC_STEPS_PER_RADIAN
+            };
+
+            // Copies at the rotations of the last few hundredths of a second,
+            // fading out as the wheel slows down. Painted first, so they trail
+            // behind the slices and their labels.
+            let blur = (self.angular_velocity.abs() / BLUR_FULL_VELOCITY).min(1.0);
+            if self.motion_blur && blur > 0.1 {
+                for k in 1..=3 {
+                    let lag = self.angular_velocity * BLUR_SECONDS * k as f32 / 3.0;
+                    let alpha = blur * 0.3 / k as f32;
+                    for slice in self.geometry.slices() {
+                        let gap = self.segment_gap.min(slice.width());
+                        ui.painter().add(egui::Shape::convex_polygon(
+                            pie_slice_points(
+                                center,
+                                outer_radius,
+                                slice.start_angle(self.rotation - lag) + gap * 0.5,
+                                slice.width() - gap,
+                                arc_steps,
+                            ),
+                            self.segments[slice.index].color.gamma_multiply(alpha),
+                            egui::Stroke::NONE,
+                        ));
+                    }
+                }
+            }
+
+            for slice in self.geometry.slices() {
+                let seg = &self.segments[slice.index];
+                // Narrower by half the gap on each side; a slice thinner than
+                // the gap isn't drawn
+                let gap = self.segment_gap.min(slice.width());
+                let angle = slice.start_angle(self.rotation) + gap * 0.5;
+                let width = slice.width() - gap;
+                if width <= 0.0 {
+                    continue;
+                }
+                let points = pie_slice_points(center, outer_radius, angle, width, arc_steps);
+
+                let stroke = if self.show_segments_borders {
+                    egui::Stroke::new(1.0, egui::Color32::BLACK)
+                } else {
+                    egui::Stroke::new(1.0, seg.color)
+                };
+
+                ui.painter()
+                    .add(egui::Shape::convex_polygon(points, seg.color, stroke));
+
+                if self.colorblind_patterns {
+                    let ink = if is_bright(seg.color) {
+                        egui::Color32::from_black_alpha(110)
+                    } else {
+                        egui::Color32::from_white_alpha(110)
+                    };
+                    ui.painter().extend(slice_pattern_shapes(
+                        slice.index,
+                        center,
+                        inner_radius,
+                        outer_radius,
+                        angle,
+                        width,
+                        ink,
+                    ));
+                }
+
+                // Text drawing logic - skips if size is 0 or the slice is too
+                // narrow for a line of text at the label radius
+                let font_size = seg.font_size.unwrap_or(self.label_font_size);
+                if font_size > 0.0 && labels_fit && text_r * width >= font_size * 0.8 {
+                    let text_a = angle + width * 0.5;
+                    let text_pos = egui::pos2(
+                        center.x + text_r * text_a.cos(),
+                        center.y + text_r * text_a.sin(),
+                    );
+                    self.draw_label(ui.painter(), slice.index, font_size, text_pos);
+                }
+            }
+
+            for ghost in &self.removing {
+                let s = (ghost.elapsed / ELIMINATE_SECONDS).min(1.0);
+                let c = ghost.color;
+                let gray = egui::Color32::from_gray(
+                    (0.299 * c.r() as f32 + 0.587 * c.g() as f32 + 0.114 * c.b() as f32) as u8,
+                );
+                let radius = inner_radius + (outer_radius - inner_radius) * (1.0 - s);
+                ui.painter().add(egui::Shape::convex_polygon(
+                    pie_slice_points(
+                        center,
+                        radius,
+                        ghost.bounds.start_angle(self.rotation),
+                        ghost.bounds.width(),
+                        arc_steps,
+                    ),
+                    c.lerp_to_gamma(gray, (s * 2.0).min(1.0))
+                        .gamma_multiply(1.0 - s),
+                    egui::Stroke::NONE,
+                ));
+            }
+
+            ui.painter().circle(
+                center,
+                inner_radius,
+                self.center_color,
+                egui::Stroke::new(2.0, egui::Color32::BLACK),
+            );
+
+            // The winning index is cleared as the next spin starts
+            if self.winner_in_center
+                && let Some(index) = self.winning_index
+                && let Some(image) = self.segments[index].image.as_mut()
+                && let Some(texture) = image.texture(ctx)
+            {
+                ui.painter()
+                    .add(circle_image_mesh(texture.id(), center, inner_radius - 1.0));
+                ui.painter().circle_stroke(
+                    center,
+                    inner_radius,
+                    egui::Stroke::new(2.0, egui::Color32::BLACK),
+                );
+            }
+
+            if self.pointer_angles.is_empty() {
+                ui.painter().add(egui::Shape::convex_polygon(
+                    pointer_triangle(center, outer_radius, self.pointer_angle),
+                    pointer_color,
+                    egui::Stroke::new(2.0, egui::Color32::BLACK),
+                ));
+            }
+            for &angle in &self.pointer_angles {
+                ui.painter().add(egui::Shape::convex_polygon(
+                    pointer_triangle(center, outer_radius, angle),
+                    self.segments[self.segment_at_angle(angle)].color,
+                    egui::Stroke::new(2.0, egui::Color32::BLACK),
+                ));
+            }
+
+            if self.tilt_scale < 1.0 {
+                self.tilt_shapes(ctx, layer, first_shape, center);
+            }
+        }
+
+        if self.spin_count == 0 && !self.start_hint.is_empty() {
+            // A steady hint needs no repaints
+            let pulse = if self.reduced_motion {
+                1.0
+            } else {
+                0.65 + 0.35 * (ctx.input(|i| i.time) as f32 * 3.0).sin()
+            };
+            let hint_galley = ui.painter().layout_no_wrap(
+                self.start_hint.clone(),
+                egui::FontId::proportional(24.0),
+                egui::Color32::WHITE.gamma_multiply(pulse),
+            );
+            let hint_rect = egui::Align2::CENTER_CENTER
+                .anchor_size(center, hint_galley.size())
+                .expand(6.0);
+
+            ui.painter().rect_filled(
+                hint_rect,
+                4.0,
+                egui::Color32::from_black_alpha((160.0 * pulse) as u8),
+            );
+            ui.painter()
+                .galley(hint_rect.shrink(6.0).min, hint_galley, egui::Color32::WHITE);
+            if !self.reduced_motion {
+                self.request_animation_frame(ctx);
+            }
+        }
+
+        self.draw_confetti(ui.painter(), center, outer_radius);
+
+        if self.cooldown_notice && self.cooldown_remaining > 0.0 {
+            ui.painter().text(
+                egui::pos2(center.x, rect.bottom() - 40.0),
+                egui::Align2::CENTER_BOTTOM,
+                "Please wait…",
+                egui::FontId::proportional(18.0),
+                egui::Color32::WHITE,
+            );
+        }
+
+        if self.practice {
+            ui.painter().text(
+                egui::pos2(center.x, rect.top() + 40.0),
+                egui::Align2::CENTER_TOP,
+                "PRACTICE",
+                egui::FontId::proportional(28.0),
+                egui::Color32::from_white_alpha(90),
+            );
+        }
+
+        if self.paused {
+            ui.painter().text(
+                center,
+                egui::Align2::CENTER_CENTER,
+                "PAUSED",
+                egui::FontId::proportional(32.0),
+                egui::Color32::WHITE,
+            );
+        }
+
+        if self.template_notice_timer > 0.0 {
+            ui.painter().text(
+                egui::pos2(center.x, rect.bottom() - 20.0),
+                egui::Align2::CENTER_BOTTOM,
+                format!(
+                    "Message {}/{}: {}",
+                    self.active_template + 1,
+                    self.winner_templates.len(),
+                    self.winner_templates[self.active_template].replace('\n', " ")
+                ),
+                egui::FontId::proportional(16.0),
+                egui::Color32::WHITE,
+            );
+        }
+
+        let wheel_bottom = center.y
+            + outer_radius
+            + if self.show_rim {
+                self.rim_thickness
+            } else {
+                0.0
+            };
+
+        if let Some(message) = self.winner_message() {
+            let mut text = egui::RichText::new(message.clone())
+                .size(self.winner_font_size)
+                .strong()
+                .color(egui::Color32::WHITE);
+            if self.winner_card.is_none() {
+                text = text.background_color(egui::Color32::from_black_alpha(200));
+            }
+            let text_size = ui
+                .painter()
+                .layout_no_wrap(
+                    message.clone(),
+                    egui::FontId::proportional(self.winner_font_size),
+                    egui::Color32::WHITE,
+                )
+                .size();
+            let card_padding = self.winner_card.as_ref().map_or(0.0, |card| card.padding);
+
+            let placement = match self.winner_position {
+                WinnerPosition::Center => None,
+                WinnerPosition::Top => Some((
+                    egui::Align2::CENTER_TOP,
+                    egui::pos2(rect.center().x, rect.top() + 10.0),
+                )),
+                WinnerPosition::Bottom => Some((
+                    egui::Align2::CENTER_BOTTOM,
+                    egui::pos2(rect.center().x, rect.bottom() - 10.0),
+                )),
+                WinnerPosition::BelowWheel => Some((
+                    egui::Align2::CENTER_TOP,
+                    egui::pos2(center.x, wheel_bottom + 8.0),
+                )),
+            };
+
+            // No button once the spin limit is reached, the summary takes over
+            let can_spin_again = !self.is_spinning && !self.spins_exhausted();
+
+            // Off-center placements keep the button right under the message
+            if let Some((pivot, pos)) = placement {
+                egui::Area::new(ui.id().with("winner_message"))
+                    .pivot(pivot)
+                    .fixed_pos(pos)
+                    .show(ctx, |ui| {
+                        ui.vertical_centered(|ui| {
+                            ui.add_space(card_padding);
+                            self.winner_label(ui, text, text_size);
+                            ui.add_space(card_padding);
+                            if can_spin_again && ui.button("Spin Again").clicked() {
+                                spin_again = true;
+                            }
+                        });
+                    });
+            } else {
+                let message_height = text_size.y;
+
+                ui.centered_and_justified(|ui| {
+                    self.winner_label(ui, text, text_size);
+                });
+
+                if can_spin_again {
+                    egui::Area::new(ui.id().with("spin_again"))
+                        .pivot(egui::Align2::CENTER_TOP)
+                        .fixed_pos(
+                            rect.center()
+                                + egui::vec2(0.0, message_height * 0.5 + card_padding + 12.0),
+                        )
+                        .show(ctx, |ui| {
+                            if ui.button("Spin Again").clicked() {
+                                spin_again = true;
+                            }
+                        });
+                }
+            }
+        }
+
+        // The click may already have started a spin through the wheel hit area
+        if spin_again && !self.is_spinning {
+            self.start_spin();
+        }
+
+        if self.show_legend {
+            self.draw_legend(ctx, ui.id().with("legend"), rect);
+        }
+
+        if self.show_summary {
+            self.draw_summary(ctx, ui.id().with("summary"), rect);
+        }
+
+        if self.show_stats {
+            self.draw_stats(ctx, ui.id().with("stats"), rect);
+        }
+
+        if let Some(filter) = self.tag_filter {
+            egui::Area::new(ui.id().with("tag_filter"))
+                .pivot(egui::Align2::RIGHT_BOTTOM)
+                .fixed_pos(rect.right_bottom() + egui::vec2(-10.0, -10.0))
+                .show(ctx, |ui| {
+                    egui::Frame::popup(ui.style()).show(ui, |ui| {
+                        ui.label(format!("Filter: {}", self.tag_filters[filter]));
+                    });
+                });
+        }
+
+        if self.show_remaining_count {
+            egui::Area::new(ui.id().with("remaining_count"))
+                .pivot(egui::Align2::RIGHT_TOP)
+                .fixed_pos(rect.right_top() + egui::vec2(-10.0, 10.0))
+                .show(ctx, |ui| {
+                    egui::Frame::popup(ui.style()).show(ui, |ui| {
+                        ui.label(format!("{} remaining", self.geometry.slices().len()));
+                    });
+                });
+        }
+
+        if self.show_ticker {
+            self.draw_ticker(ui.painter(), rect);
+        }
+
+        if self.show_fairness_info && self.spin_count > 0 {
+            egui::Area::new(ui.id().with("fairness_info"))
+                .pivot(egui::Align2::LEFT_BOTTOM)
+                .fixed_pos(rect.left_bottom() + egui::vec2(10.0, -10.0))
+                .show(ctx, |ui| {
+                    egui::Frame::popup(ui.style()).show(ui, |ui| {
+                        ui.label(format!("Spin #{}", self.spin_count));
+                        ui.label(match self.seed {
+                            Some(seed) => format!("Seed: {seed}"),
+                            None => "Seed: none (system entropy)".to_string(),
+                        });
+                        ui.label(format!("Target rotation: {:.4} rad", self.target_rotation));
+                        match self.winning_index {
+                            Some(index) => ui.label(format!(
+                                "Landed index: {index} ({})",
+                                self.segments[index].label
+                            )),
+                            None => ui.label("Landed index: pending"),
+                        };
+                    });
+                });
+        }
+
+        if let Some(charge) = self.power_charge {
+            let meter = egui::Rect::from_center_size(
+                egui::pos2(center.x, rect.bottom() - 60.0),
+                egui::vec2(200.0, 14.0),
+            );
+            let mut fill = meter;
+            fill.set_width(meter.width() * charge);
+            ui.painter()
+                .rect_filled(meter, 4.0, egui::Color32::from_black_alpha(160));
+            ui.painter().rect_filled(
+                fill,
+                4.0,
+                egui::Color32::from_rgb(60, 200, 80).lerp_to_gamma(egui::Color32::RED, charge),
+            );
+            ui.painter().rect_stroke(
+                meter,
+                4.0,
+                egui::Stroke::new(1.5, egui::Color32::WHITE),
+                egui::StrokeKind::Outside,
+            );
+        }
+    }
+
+    // One line of every label, repeated end to end so the scroll wraps seamlessly
+    fn draw_ticker(&mut self, painter: &egui::Painter, rect: egui::Rect) {
+        let text: String = self
+            .active_segments()
+            .map(|(index, _)| format!("{}   •   ", self.slice_text(index)))
+            .collect();
+        let galley = painter.layout_no_wrap(
+            text,
+            egui::FontId::proportional(TICKER_FONT_SIZE),
+            egui::Color32::WHITE,
+        );
+        let width = galley.size().x;
+        if width <= 0.0 {
+            return;
+        }
+        self.ticker_offset = self.ticker_offset.rem_euclid(width);
+
+        let band = egui::Rect::from_min_max(
+            egui::pos2(rect.left(), rect.bottom() - galley.size().y - 8.0),
+            rect.right_bottom(),
+        );
+        painter.rect_filled(band, 0.0, egui::Color32::from_black_alpha(140));
+
+        let painter = painter.with_clip_rect(band);
+        let mut x = band.left() - self.ticker_offset;
+        while x < band.right() {
+            painter.galley(
+                egui::pos2(x, band.top() + 4.0),
+                galley.clone(),
+                egui::Color32::WHITE,
+            );
+            x += width;
+        }
+    }
+}
+
+impl App for OverlayApp {
+    fn clear_color(&self, _: &egui::Visuals) -> [f32; 4] {
+        [0.0, 0.0, 0.0, 0.0]
+    }
+
+    fn update(&mut self, ctx: &egui::Context, _: &mut Frame) {
+        egui::CentralPanel::default()
+            .frame(egui::Frame::NONE.fill(egui::Color32::TRANSPARENT))
+            .show(ctx, |ui| self.show(ctx, ui));
+    }
+
+    fn on_exit(&mut self, _: Option<&eframe::glow::Context>) {
+        self.write_stats();
+    }
+}
+
+pub(crate) fn write_stats_json(path: &str, stats: &impl serde::Serialize) {
+    let result = serde_json::to_string_pretty(stats)
+        .map_err(|e| e.to_string())
+        .and_then(|json| fs::write(path, json + "\n").map_err(|e| e.to_string()));
+    if let Err(e) = result {
+        eprintln!("warning: failed to write stats '{path}': {e}");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::AppConfigBuilder;
+
+    // Frame time of the stepped spins, a steady 60 fps
+    const DT: f32 = 1.0 / 60.0;
+
+    // Runs without sound, so the tests don't need an output device
+    fn headless(config: AppConfig) -> OverlayApp {
+        let mut app = OverlayApp::from_config(config);
+        app.music_sink = None;
+        app.audio_handle = None;
+        app._audio_stream = None;
+        app
+    }
+
+    fn seeded(seed: u64) -> AppConfig {
+        AppConfigBuilder::new()
+            .add_segment("Red", 1, Some("#FF0000"))
+            .add_segment("Green", 2, Some("#00FF00"))
+            .add_segment("Blue", 3, Some("#0000FF"))
+            .spin_duration_ms(1000.0)
+            .seed(seed)
+            .build()
+            .expect("valid config")
+    }
+
+    // Steps `dt` at a time until the spin has finished, failing after a minute
+    fn spin_to_end(app: &mut OverlayApp, dt: f32) {
+        assert!(app.spin(), "the wheel should start spinning");
+        let mut elapsed = 0.0;
+        while app.is_spinning {
+            app.step(dt);
+            elapsed += dt;
+            assert!(elapsed < 60.0, "the spin never finished");
+        }
+    }
+
+    fn winner(app: &OverlayApp) -> &str {
+        let index = app.winning_index.expect("a finished spin has a winner");
+        &app.segments[index].label
+    }
+
+    #[test]
+    fn seeded_spin_lands_on_known_winner() {
+        let mut app = headless(seeded(42));
+        spin_to_end(&mut app, DT);
+        assert_eq!(winner(&app), "Green");
+    }
+
+    #[test]
+    fn same_seed_replays_the_same_results() {
+        let results = |seed| {
+            let mut app = headless(seeded(seed));
+            (0..5)
+                .map(|_| {
+                    spin_to_end(&mut app, DT);
+                    winner(&app).to_string()
+                })
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(results(7), results(7));
+    }
+
+    #[test]
+    fn long_final_frame_still_lands_on_the_target() {
+        let mut app = headless(seeded(3));
+        assert!(app.spin());
+        let target = app
+            .geometry
+            .segment_at(app.target_rotation, app.pointer_angle)
+            .expect("the wheel has segments");
+
+        // Most of the spin at 60 fps, then one frame far past the end
+        for _ in 0..50 {
+            app.step(DT);
+        }
+        app.step(5.0);
+        while app.is_spinning {
+            app.step(DT);
+        }
+
+        assert_eq!(app.rotation, app.target_rotation);
+        assert_eq!(app.winning_index, Some(target));
+    }
+
+    #[test]
+    fn stalls_keep_the_spin_to_wall_time() {
+        // Wall time until the spin is over, with a half second stall every
+        // `stall_every` frames, all else at 60 fps
+        let wall_time = |stall_every: usize| {
+            let config = AppConfigBuilder::new()
+                .add_segment("Red", 1, None)
+                .add_segment("Blue", 1, None)
+                .spin_duration_ms(3000.0)
+                .seed(11)
+                .with(|c| c.max_frame_dt = Some(0.1))
+                .build()
+                .expect("valid config");
+            let mut app = headless(config);
+            assert!(app.spin());
+            let mut wall = 0.0;
+            for frame in 1.. {
+                let frame_dt = if frame % stall_every == 0 { 0.5 } else { DT };
+                wall += frame_dt;
+                app.advance(frame_dt);
+                if !app.is_spinning {
+                    return wall;
+                }
+                assert!(wall < 60.0, "the spin never finished");
+            }
+            unreachable!()
+        };
+
+        let steady = wall_time(usize::MAX);
+        assert!(
+            (steady - 3.0).abs() < 2.0 * DT,
+            "steady spin took {steady}s"
+        );
+        // Dropping the time beyond max_frame_dt would lose 0.4s per stall
+        let stalled = wall_time(25);
+        assert!((stalled - 3.0).abs() < 0.15, "stalled spin took {stalled}s");
+    }
+
+    // The winner of a spin made to stop with the pointer exactly on the
+    // boundary between the first two of four equal slices
+    fn boundary_winner(policy: &str, seed: u64) -> String {
+        let config = AppConfigBuilder::new()
+            .add_segment("A", 1, None)
+            .add_segment("B", 1, None)
+            .add_segment("C", 1, None)
+            .add_segment("D", 1, None)
+            .spin_duration_ms(500.0)
+            .seed(seed)
+            .with(|c| c.boundary_tiebreak = Some(policy.to_string()))
+            .build()
+            .expect("valid config");
+        let mut app = headless(config);
+        assert!(app.spin());
+        app.target_rotation = app.pointer_angle - PI / 2.0;
+        assert_eq!(
+            app.geometry
+                .boundary_at(app.target_rotation, app.pointer_angle),
+            Some((0, 1))
+        );
+        while app.is_spinning {
+            app.step(DT);
+        }
+        winner(&app).to_string()
+    }
+
+    #[test]
+    fn lower_tiebreak_picks_the_earlier_slice() {
+        assert_eq!(boundary_winner("lower", 1), "A");
+    }
+
+    #[test]
+    fn higher_tiebreak_picks_the_later_slice() {
+        assert_eq!(boundary_winner("higher", 1), "B");
+    }
+
+    #[test]
+    fn random_tiebreak_picks_either_slice_by_seed() {
+        let winners: Vec<String> = (0..16)
+            .map(|seed| boundary_winner("random", seed))
+            .collect();
+        assert!(winners.iter().all(|w| w == "A" || w == "B"), "{winners:?}");
+        assert!(winners.iter().any(|w| w == "A") && winners.iter().any(|w| w == "B"));
+        assert_eq!(boundary_winner("random", 5), boundary_winner("random", 5));
+    }
+
+    #[test]
+    fn winner_message_keeps_the_untruncated_label() {
+        let config = AppConfigBuilder::new()
+            .add_segment("Weekend getaway", 1, None)
+            .add_segment("Weekend getaway", 1, None)
+            .spin_duration_ms(500.0)
+            .seed(1)
+            .with(|c| c.label_max_chars = Some(7))
+            .build()
+            .expect("valid config");
+        let mut app = headless(config);
+        spin_to_end(&mut app, DT);
+
+        let message = app.winner_message().expect("a winner message");
+        assert!(message.contains("Weekend getaway"), "{message}");
+    }
+
+    #[test]
+    fn strict_assets_rejects_a_missing_file() {
+        let missing = std::env::temp_dir().join("rheel-test-missing-tick.wav");
+        // Set after building, the builder would already reject the strict config
+        let config = |strict: bool| AppConfig {
+            strict_assets: Some(strict),
+            ..AppConfigBuilder::new()
+                .add_segment("Red", 1, None)
+                .add_segment("Blue", 1, None)
+                .with(|c| c.tick_sounds = Some(vec![missing.to_string_lossy().into_owned()]))
+                .build()
+                .expect("valid config")
+        };
+
+        let report = match OverlayApp::try_new(config(true)) {
+            Ok(_) => panic!("a strict config with a missing asset was accepted"),
+            Err(report) => report,
+        };
+        let path = missing.display().to_string();
+        assert!(
+            report.errors.iter().any(|e| e.contains(&path)),
+            "{:?}",
+            report.errors
+        );
+
+        // Without strict mode the sound just falls back
+        assert!(OverlayApp::try_new(config(false)).is_ok());
+    }
+
+    #[test]
+    fn long_labels_wrap_at_label_wrap_width() {
+        let label = "An extraordinarily long prize description";
+        let rows = |wrap_width: Option<f32>| {
+            let config = AppConfigBuilder::new()
+                .add_segment(label, 1, None)
+                .add_segment("Short", 1, None)
+                .with(|c| c.label_wrap_width = wrap_width)
+                .build()
+                .expect("valid config");
+            let app = headless(config);
+            let mut rows = 0;
+            let _ = egui::Context::default().run(egui::RawInput::default(), |ctx| {
+                let painter = ctx.layer_painter(egui::LayerId::background());
+                let galley = app.layout_label(&painter, label, 16.0, egui::Color32::WHITE);
+                rows = galley.rows.len();
+            });
+            rows
+        };
+
+        assert_eq!(rows(None), 1);
+        assert!(rows(Some(80.0)) > 1, "the label didn't wrap");
+    }
+
+    #[test]
+    fn reversed_order_mirrors_the_segment_under_the_pointer() {
+        let pointed_at = |reverse: bool, slot: usize| {
+            let config = AppConfigBuilder::new()
+                .add_segment("A", 1, None)
+                .add_segment("B", 1, None)
+                .add_segment("C", 1, None)
+                .add_segment("D", 1, None)
+                .with(|c| c.reverse_segment_order = Some(reverse))
+                .build()
+                .expect("valid config");
+            let mut app = headless(config);
+            // Pointer in the middle of the `slot`th quarter laid out from `rotation`
+            app.rotation = app.pointer_angle - (slot as f32 + 0.5) * PI / 2.0;
+            app.get_current_segment_info().1.to_string()
+        };
+
+        for (slot, expected) in ["A", "B", "C", "D"].into_iter().enumerate() {
+            assert_eq!(pointed_at(false, slot), expected);
+            assert_eq!(pointed_at(true, 3 - slot), expected);
+        }
+    }
+}
@@ -0,0 +1,78 @@
+use eframe::egui;
+use std::path::Path;
+
+// --- IMAGE ASSETS ---
+
+pub(crate) fn load_color_image(path: &Path) -> Option<egui::ColorImage> {
+    match image::open(path) {
+        Ok(image) => {
+            let rgba = image.to_rgba8();
+            let size = [rgba.width() as usize, rgba.height() as usize];
+            Some(egui::ColorImage::from_rgba_unmultiplied(
+                size,
+                rgba.as_raw(),
+            ))
+        }
+        Err(e) => {
+            eprintln!("warning: failed to load image '{}': {e}", path.display());
+            None
+        }
+    }
+}
+
+// An image split into a grid of equally sized frames, played left to right and
+// top to bottom. A single frame is just a static image. The texture is uploaded
+// on first use since that needs the egui context.
+pub(crate) struct SpriteSheet {
+    name: String,
+    pending: Option<egui::ColorImage>,
+    texture: Option<egui::TextureHandle>,
+    frames: u32,
+    columns: u32,
+    fps: f32,
+}
+
+impl SpriteSheet {
+    pub(crate) fn load(path: &Path, frames: u32, columns: u32, fps: f32) -> Option<Self> {
+        let frames = frames.max(1);
+        Some(Self {
+            name: path.display().to_string(),
+            pending: Some(load_color_image(path)?),
+            texture: None,
+            frames,
+            columns: columns.clamp(1, frames),
+            fps: fps.max(0.0),
+        })
+    }
+
+    pub(crate) fn is_animated(&self) -> bool {
+        self.frames > 1 && self.fps > 0.0
+    }
+
+    pub(crate) fn frame_interval(&self) -> f32 {
+        1.0 / self.fps
+    }
+
+    pub(crate) fn texture(&mut self, ctx: &egui::Context) -> Option<&egui::TextureHandle> {
+        if let Some(image) = self.pending.take() {
+            self.texture = Some(ctx.load_texture(&self.name, image, Default::default()));
+        }
+        self.texture.as_ref()
+    }
+
+    // UV rectangle of the frame showing at `time` seconds
+    pub(crate) fn frame_uv(&self, time: f64) -> egui::Rect {
+        let frame = if self.is_animated() {
+            (time * self.fps as f64) as u32 % self.frames
+        } else {
+            0
+        };
+        let rows = self.frames.div_ceil(self.columns);
+        let cell = egui::vec2(1.0 / self.columns as f32, 1.0 / rows as f32);
+        let min = egui::pos2(
+            (frame % self.columns) as f32 * cell.x,
+            (frame / self.columns) as f32 * cell.y,
+        );
+        egui::Rect::from_min_size(min, cell)
+    }
+}
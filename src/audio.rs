@@ -0,0 +1,148 @@
+use rodio::cpal::traits::{DeviceTrait, HostTrait};
+use rodio::{Decoder, OutputStream, OutputStreamHandle, Sink, cpal, source::Source};
+use std::fs;
+use std::io::Cursor;
+use std::path::Path;
+use std::sync::Arc;
+use std::time::Duration;
+
+// --- AUDIO HELPERS ---
+
+fn output_devices() -> Vec<cpal::Device> {
+    cpal::default_host()
+        .output_devices()
+        .map(|devices| devices.collect())
+        .unwrap_or_default()
+}
+
+/// Names of the audio output devices, as `audio_device` takes them.
+pub fn output_device_names() -> Vec<String> {
+    output_devices()
+        .iter()
+        .filter_map(|d| d.name().ok())
+        .collect()
+}
+
+// Opens the output device called `name`. Falls back to the default device, and
+// to no sound at all without one (e.g. headless).
+pub(crate) fn open_output_stream(name: Option<&str>) -> Option<(OutputStream, OutputStreamHandle)> {
+    if let Some(name) = name {
+        match output_devices()
+            .iter()
+            .find(|d| d.name().is_ok_and(|n| n == name))
+            .map(OutputStream::try_from_device)
+        {
+            Some(Ok(stream)) => return Some(stream),
+            Some(Err(e)) => eprintln!("warning: failed to open audio device '{name}': {e}"),
+            None => eprintln!("warning: audio device '{name}' not found, using the default"),
+        }
+    }
+    match OutputStream::try_default() {
+        Ok(stream) => Some(stream),
+        Err(e) => {
+            eprintln!("warning: failed to initialize audio, running without sound: {e}");
+            None
+        }
+    }
+}
+
+// An encoded sound file kept in memory so it can be decoded again for every play
+#[derive(Clone)]
+pub(crate) struct SoundClip {
+    bytes: Arc<[u8]>,
+}
+
+impl SoundClip {
+    pub(crate) fn load(path: &Path) -> Option<Self> {
+        match fs::read(path) {
+            Ok(bytes) => Some(Self {
+                bytes: bytes.into(),
+            }),
+            Err(e) => {
+                eprintln!("warning: failed to load sound '{}': {e}", path.display());
+                None
+            }
+        }
+    }
+
+    // Fire-and-forget playback. Every playback does nothing without a handle.
+    pub(crate) fn play(&self, handle: Option<&OutputStreamHandle>, volume: f32) {
+        if let Some(sink) = self.start(handle, volume) {
+            sink.detach();
+        }
+    }
+
+    // Fire-and-forget playback, `speed` above 1.0 also raises the pitch
+    pub(crate) fn play_at_speed(
+        &self,
+        handle: Option<&OutputStreamHandle>,
+        volume: f32,
+        speed: f32,
+    ) {
+        if let Some(sink) = self.start_at_speed(handle, volume, speed) {
+            sink.detach();
+        }
+    }
+
+    // Playback on a sink owned by the caller, so it can be stopped early
+    pub(crate) fn start(&self, handle: Option<&OutputStreamHandle>, volume: f32) -> Option<Sink> {
+        self.start_at_speed(handle, volume, 1.0)
+    }
+
+    // Playback repeating until the caller stops or drops the sink
+    pub(crate) fn start_looped(
+        &self,
+        handle: Option<&OutputStreamHandle>,
+        volume: f32,
+    ) -> Option<Sink> {
+        let sink = Sink::try_new(handle?).ok()?;
+        match Decoder::new(Cursor::new(self.bytes.clone())) {
+            Ok(source) => {
+                sink.set_volume(volume);
+                sink.append(source.repeat_infinite());
+                Some(sink)
+            }
+            Err(e) => {
+                eprintln!("warning: failed to decode sound: {e}");
+                None
+            }
+        }
+    }
+
+    fn start_at_speed(
+        &self,
+        handle: Option<&OutputStreamHandle>,
+        volume: f32,
+        speed: f32,
+    ) -> Option<Sink> {
+        let sink = Sink::try_new(handle?).ok()?;
+        match Decoder::new(Cursor::new(self.bytes.clone())) {
+            Ok(source) => {
+                sink.set_volume(volume);
+                sink.set_speed(speed);
+                sink.append(source);
+                Some(sink)
+            }
+            Err(e) => {
+                eprintln!("warning: failed to decode sound: {e}");
+                None
+            }
+        }
+    }
+}
+
+pub(crate) fn play_tone(
+    handle: Option<&OutputStreamHandle>,
+    frequency: f32,
+    millis: u64,
+    volume: f32,
+) {
+    if let Some(Ok(sink)) = handle.map(Sink::try_new) {
+        let source = rodio::source::SineWave::new(frequency)
+            .take_duration(Duration::from_millis(millis))
+            .amplify(volume);
+
+        sink.append(source);
+        sink.detach();
+    }
+}
@@ -0,0 +1,45 @@
+use std::process::Command;
+use std::thread;
+
+// --- WIN COMMAND ---
+
+// Runs `command` through the system shell after a spin. The winning labels
+// are never spliced into the command text, where the shell would parse
+// them: `{winner}` becomes a reference to the RHEEL_WINNER environment
+// variable holding them, which the shell expands without interpreting it.
+// Under `sh` each winner is also a positional parameter ($1, $2, ...). The
+// command itself comes from the config, so whoever writes that can still run
+// anything. Waits in a background thread and only logs failures.
+pub(crate) fn run_on_win(command: &str, winners: &[&str]) {
+    let winner = winners.join(", ");
+    let winners: Vec<String> = winners.iter().map(|w| w.to_string()).collect();
+    let command = command.replace("{winner}", WINNER_REFERENCE);
+    thread::spawn(move || {
+        let mut shell = if cfg!(windows) {
+            // Delayed expansion substitutes the variable after the line is parsed
+            let mut shell = Command::new("cmd");
+            shell.args(["/V:ON", "/C", &command]);
+            shell
+        } else {
+            let mut shell = Command::new("sh");
+            shell.args(["-c", &command, "rheel"]).args(&winners);
+            shell
+        };
+        match shell.env("RHEEL_WINNER", &winner).output() {
+            Ok(output) if output.status.success() => {}
+            Ok(output) => eprintln!(
+                "warning: on_win_command '{command}' failed ({}): {}",
+                output.status,
+                String::from_utf8_lossy(&output.stderr).trim()
+            ),
+            Err(e) => eprintln!("warning: failed to run on_win_command '{command}': {e}"),
+        }
+    });
+}
+
+// What `{winner}` turns into, quoted so a label stays one word under `sh`
+const WINNER_REFERENCE: &str = if cfg!(windows) {
+    "!RHEEL_WINNER!"
+} else {
+    "\"$RHEEL_WINNER\""
+};
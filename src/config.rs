@@ -0,0 +1,1378 @@
+use crate::helpers::{
+    gcd, is_valid_bezier, parse_boundary_tiebreak, parse_hex_color, parse_label_direction,
+    parse_layout, parse_mouse_button, parse_odds_format, parse_segment_sort, parse_theme,
+    parse_tick_mode, parse_winner_position,
+};
+use crate::import::parse_segments_csv;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+pub(crate) const MIN_UI_SCALE: f32 = 0.5;
+pub(crate) const MAX_UI_SCALE: f32 = 3.0;
+// Steeper tilts squash the wheel too far to read
+pub(crate) const MAX_TILT_DEGREES: f32 = 75.0;
+
+// --- CONFIGURATION STRUCTS ---
+
+#[derive(Clone, Deserialize, Serialize, Debug)]
+pub struct SegmentConfig {
+    pub label: String,
+    // Left out (0) when the segment gives a `probability` instead
+    #[serde(default)]
+    pub weight: u32,
+    // Chance of winning in percent, in place of a weight. Segments with weights
+    // share whatever the probabilities leave of 100%, in proportion to them.
+    pub probability: Option<f32>,
+    pub color: Option<String>,
+    // Overrides `label_font_size` for this segment's label
+    pub font_size: Option<f32>,
+    // A "try again" slice: it can be landed on but is never reported as a winner
+    pub is_blank: Option<bool>,
+    // Names the T key filters on, e.g. ["vip"]
+    pub tags: Option<Vec<String>>,
+    // Picture of the entrant, shown in the hub after a win with `winner_in_center`
+    pub image: Option<String>,
+    // Name of another of the `wheels`, spun automatically after this segment wins
+    pub chain_to: Option<String>,
+}
+
+/// One of several wheels shown side by side. Everything but the segments and
+/// title is shared from the enclosing [`AppConfig`].
+#[derive(Clone, Deserialize, Serialize, Debug)]
+pub struct WheelConfig {
+    // What `chain_to` on a segment refers to this wheel by
+    pub name: Option<String>,
+    pub title_text: Option<String>,
+    pub segments_csv: Option<String>,
+    #[serde(default)]
+    pub segments: Vec<SegmentConfig>,
+}
+
+#[derive(Clone, Deserialize, Serialize, Debug)]
+pub struct AppConfig {
+    // "wheel", "reel", a vertical slot machine strip, or "picker", just the
+    // name under the pointer in large text
+    pub layout: Option<String>,
+    pub spin_duration_ms: f32,
+    // Safeguard against runaway spins: past this the wheel stops where it is
+    pub max_spin_seconds: Option<f32>,
+    // Seeds the spin randomness, so the same seed replays the same results
+    pub seed: Option<u64>,
+    // Cubic-bezier control points x1, y1, x2, y2 for the spin curve, as in CSS
+    pub easing_bezier: Option<[f32; 4]>,
+    pub scale_duration_with_spins: Option<bool>,
+    // Holding Space charges a meter, the longer the hold the more turns the spin makes
+    pub power_spin: Option<bool>,
+    // The wheel rocks back a little before launching into a spin
+    pub windup: Option<bool>,
+    // Short spins of less than a turn, without the zoom, bounce and removal animations
+    pub reduced_motion: Option<bool>,
+    pub auto_spin_after_ms: Option<f32>,
+    // New spins are ignored for this long after a result, against double presses
+    pub spin_cooldown_ms: Option<f32>,
+    // Time from one spin's result to the next spin, repeating indefinitely
+    pub auto_spin_interval_ms: Option<f32>,
+    // Spins before the wheel locks and shows the summary of all winners
+    pub max_spins_allowed: Option<u32>,
+    pub countdown_cues: Option<bool>,
+    pub countdown_sound_path: Option<String>,
+    pub center_color: Option<String>,
+    pub center_radius_ratio: Option<f32>,
+    // Shift of the wheel from the window center, in points
+    pub center_offset_x: Option<f32>,
+    pub center_offset_y: Option<f32>,
+    pub winner_message: Option<String>,
+    pub winner_messages: Option<Vec<String>>,
+    pub winners_per_spin: Option<usize>,
+    // Remove each winner from the wheel when the next spin starts
+    pub elimination_mode: Option<bool>,
+    pub eliminate_animation: Option<bool>,
+    // Draw winners from a shuffled bag holding `weight` tickets per segment, so
+    // every segment wins its share once the bag is used up
+    pub fair_bag: Option<bool>,
+    pub blank_message: Option<String>,
+    pub winner_font_size: Option<f32>,
+    pub winner_message_position: Option<String>,
+    // A rounded card behind the winner message instead of the plain text background
+    pub winner_card_color: Option<String>,
+    pub winner_border_color: Option<String>,
+    pub winner_card_padding: Option<f32>,
+    pub label_font_size: Option<f32>,
+    pub label_direction: Option<String>,
+    pub label_shadow: Option<bool>,
+    pub label_bg_color: Option<String>,
+    // e.g. "${n:,}" shows a label of 1000 as $1,000
+    pub label_number_format: Option<String>,
+    // Longer slice labels are cut to this many characters, ending in "…"
+    pub label_max_chars: Option<usize>,
+    // Longer slice labels wrap onto more lines at this width, in points
+    pub label_wrap_width: Option<f32>,
+    // Draw "?" instead of the labels, only the winner's is shown after the spin
+    pub mystery_mode: Option<bool>,
+    pub max_labels: Option<usize>,
+    pub show_segments_borders: Option<bool>,
+    // Empty angle between neighbouring slices, for a segmented ring look.
+    // Only the drawing changes, not which slice wins.
+    pub segment_gap_degrees: Option<f32>,
+    // Rings, spokes and dots over the slice colors, so slices differ without hue
+    pub colorblind_patterns: Option<bool>,
+    // Faded copies of the slices trailing the wheel while it turns fast
+    pub motion_blur: Option<bool>,
+    pub weight_affects_color: Option<bool>,
+    pub background_image: Option<String>,
+    // Taskbar and title bar icon of the standalone app
+    pub window_icon: Option<String>,
+    // Missing images and sounds are errors that stop the app from starting,
+    // instead of being left out with a warning
+    pub strict_assets: Option<bool>,
+    // Screen edge or corner the window is moved to, e.g. "top-right". Needs the
+    // `layer-shell` feature on Linux.
+    pub overlay_anchor: Option<String>,
+    pub background_spritesheet: Option<String>,
+    pub bg_frame_count: Option<u32>,
+    pub bg_columns: Option<u32>,
+    pub bg_fps: Option<f32>,
+    pub show_rim: Option<bool>,
+    pub rim_color: Option<String>,
+    pub rim_thickness: Option<f32>,
+    // Degrees the wheel leans back, drawn as an ellipse for a 3D look; 0 is flat
+    pub tilt: Option<f32>,
+    pub show_legend: Option<bool>,
+    // How the legend and slice labels state each slice's odds: "percent",
+    // "fraction" (3/20) or "ratio" (3:17)
+    pub odds_format: Option<String>,
+    // Adds each slice's odds under its label
+    pub show_slice_odds: Option<bool>,
+    pub ui_theme: Option<String>,
+    // Finer slice outlines and multisampling, for recordings; costs some performance
+    pub high_quality: Option<bool>,
+    // Multiplies the OS scale factor, 1.0 = native size
+    pub ui_scale: Option<f32>,
+    pub show_remaining_count: Option<bool>,
+    // Marquee of all labels along the bottom edge, held still during spins
+    pub show_ticker: Option<bool>,
+    // Corner panel with the spin's random target and the landed index, for public draws
+    pub show_fairness_info: Option<bool>,
+    pub zoom_reveal: Option<bool>,
+    // A burst of confetti over the wheel when a winner is revealed
+    pub confetti: Option<bool>,
+    // Played when a winner is revealed
+    pub fanfare_sound_path: Option<String>,
+    // Delays of the confetti and the fanfare after the reveal, to line the
+    // burst up with an accent in the fanfare
+    pub confetti_delay_ms: Option<f32>,
+    pub fanfare_delay_ms: Option<f32>,
+    pub snap_winner_to_top: Option<bool>,
+    pub settle_bounce: Option<bool>,
+    pub reveal_delay_ms: Option<f32>,
+    pub drumroll_sound_path: Option<String>,
+    // Played once as a spin starts
+    pub start_sound_path: Option<String>,
+    // Repeated while the wheel is in motion, under the ticks
+    pub loop_sound_path: Option<String>,
+    // Output device name, e.g. a virtual cable; the system default otherwise
+    pub audio_device: Option<String>,
+    // Click played the moment the wheel comes to rest, a low synthesized one by default
+    pub lock_sound_path: Option<String>,
+    // Looping background music, turned down while the wheel spins
+    pub music_path: Option<String>,
+    pub tick_mode: Option<String>,
+    // Which slice wins when the pointer rests exactly on a boundary: "lower"
+    // (the earlier one clockwise), "higher" or "random"
+    pub boundary_tiebreak: Option<String>,
+    // Most boundary ticks per turn, the rest are skipped to keep big wheels from buzzing
+    pub ticks_per_revolution: Option<usize>,
+    pub tension_ticks: Option<bool>,
+    // Samples played in random order instead of the synthesized tick
+    pub tick_sounds: Option<Vec<String>>,
+    pub max_fps: Option<f32>,
+    // Longest time step (seconds) of one frame, the rest of a stall is caught up afterwards
+    pub max_frame_dt: Option<f32>,
+    pub click_to_spin: Option<bool>,
+    pub click_hub_only: Option<bool>,
+    // Shows the last winner's segment image in the hub until the next spin
+    pub winner_in_center: Option<bool>,
+    pub drag_spin: Option<bool>,
+    // Fraction of the flick speed lost per second in drag spins
+    pub friction: Option<f32>,
+    pub spin_mouse_button: Option<String>,
+    pub reverse_segment_order: Option<bool>,
+    // "config", "label", "weight_asc" or "weight_desc"; only the layout changes, not the odds
+    pub segment_sort: Option<String>,
+    pub start_hint: Option<String>,
+    pub title_text: Option<String>,
+    pub title_font_size: Option<f32>,
+    // Angles are given in degrees, clockwise from the positive x axis (270 = top)
+    pub pointer_angle_deg: Option<f32>,
+    // Several pointers, each choosing a winner when the wheel stops. The first
+    // takes the place of `pointer_angle_deg`.
+    pub pointer_positions: Option<Vec<f32>>,
+    pub initial_rotation_deg: Option<f32>,
+    // Until the first spin, turns the wheel so the first segment begins at the pointer
+    pub anchor_first_segment: Option<bool>,
+    // Minimum drawn width of a slice. Changes the look of the wheel, not the odds.
+    pub min_slice_degrees: Option<f32>,
+    pub segments_csv: Option<String>,
+    pub status_file: Option<String>,
+    // File the random inputs of every spin are appended to, "-" for stderr
+    pub audit_log: Option<String>,
+    // Spin results are POSTed here as JSON, needs the `webhook` feature
+    pub webhook_url: Option<String>,
+    // Shell command run after each spin with winners. `{winner}` expands to
+    // their labels through the RHEEL_WINNER environment variable, so labels
+    // are never parsed as shell code. The command itself runs arbitrary code,
+    // so it also needs `allow_commands`.
+    pub on_win_command: Option<String>,
+    pub allow_commands: Option<bool>,
+    // Where Ctrl+S saves the current wheel
+    pub export_path: Option<String>,
+    #[serde(default)]
+    pub segments: Vec<SegmentConfig>,
+    // Shows these wheels side by side instead of the single wheel of `segments`
+    pub wheels: Option<Vec<WheelConfig>>,
+    /// Directory of the config file. Relative asset paths (sounds, images,
+    /// `segments_csv`) are resolved against it, or the working directory if unset.
+    #[serde(skip)]
+    pub base_dir: Option<PathBuf>,
+}
+
+fn import_csv(
+    path: &Option<String>,
+    base_dir: Option<&Path>,
+    segments: &mut Vec<SegmentConfig>,
+) -> Result<Vec<String>, String> {
+    let Some(path) = path else {
+        return Ok(Vec::new());
+    };
+    let text = fs::read_to_string(resolve_path(base_dir, path))
+        .map_err(|e| format!("failed to read segments_csv '{path}': {e}"))?;
+
+    let (imported, warnings) = parse_segments_csv(&text);
+    segments.extend(imported);
+    Ok(warnings)
+}
+
+// "out/result.txt" with n = 2 becomes "out/result-2.txt"
+fn numbered_path(path: &str, n: usize) -> String {
+    let path = Path::new(path);
+    let stem = path.file_stem().unwrap_or_default().to_string_lossy();
+    let name = match path.extension() {
+        Some(ext) => format!("{stem}-{n}.{}", ext.to_string_lossy()),
+        None => format!("{stem}-{n}"),
+    };
+    path.with_file_name(name).to_string_lossy().into_owned()
+}
+
+pub(crate) fn resolve_path(base_dir: Option<&Path>, path: &str) -> PathBuf {
+    match base_dir {
+        // Joining an absolute path replaces the base
+        Some(dir) => dir.join(path),
+        None => PathBuf::from(path),
+    }
+}
+
+impl Default for AppConfig {
+    fn default() -> Self {
+        Self {
+            layout: Some("wheel".to_string()),
+            spin_duration_ms: 5000.0,
+            max_spin_seconds: None,
+            seed: None,
+            easing_bezier: None,
+            scale_duration_with_spins: Some(false),
+            power_spin: Some(false),
+            windup: Some(false),
+            reduced_motion: Some(false),
+            auto_spin_after_ms: None,
+            spin_cooldown_ms: Some(0.0),
+            auto_spin_interval_ms: None,
+            max_spins_allowed: None,
+            countdown_cues: Some(false),
+            countdown_sound_path: None,
+            center_color: Some("#202020".to_string()),
+            center_radius_ratio: Some(0.25),
+            center_offset_x: Some(0.0),
+            center_offset_y: Some(0.0),
+            winner_message: Some("Winner:\n{label}".to_string()),
+            winner_messages: None,
+            winners_per_spin: Some(1),
+            elimination_mode: Some(false),
+            eliminate_animation: Some(true),
+            fair_bag: Some(false),
+            blank_message: Some("No luck, spin again!".to_string()),
+            winner_font_size: Some(40.0),
+            winner_message_position: Some("center".to_string()),
+            winner_card_color: None,
+            winner_border_color: None,
+            winner_card_padding: Some(16.0),
+            label_font_size: Some(20.0),
+            label_direction: Some("ltr".to_string()),
+            label_shadow: Some(false),
+            label_bg_color: None,
+            label_number_format: None,
+            label_max_chars: None,
+            label_wrap_width: None,
+            mystery_mode: Some(false),
+            max_labels: None,
+            show_segments_borders: Some(true),
+            segment_gap_degrees: Some(0.0),
+            colorblind_patterns: Some(false),
+            motion_blur: Some(false),
+            weight_affects_color: Some(false),
+            background_image: None,
+            window_icon: None,
+            strict_assets: Some(false),
+            overlay_anchor: None,
+            background_spritesheet: None,
+            bg_frame_count: None,
+            bg_columns: None,
+            bg_fps: None,
+            show_rim: Some(true),
+            rim_color: None,
+            rim_thickness: Some(5.0),
+            tilt: Some(0.0),
+            show_legend: Some(false),
+            odds_format: Some("percent".to_string()),
+            show_slice_odds: Some(false),
+            ui_theme: None,
+            high_quality: Some(false),
+            ui_scale: None,
+            show_remaining_count: Some(false),
+            show_ticker: Some(false),
+            show_fairness_info: Some(false),
+            zoom_reveal: Some(false),
+            confetti: Some(false),
+            fanfare_sound_path: None,
+            confetti_delay_ms: Some(0.0),
+            fanfare_delay_ms: Some(0.0),
+            snap_winner_to_top: Some(false),
+            settle_bounce: Some(false),
+            reveal_delay_ms: Some(0.0),
+            drumroll_sound_path: None,
+            start_sound_path: None,
+            loop_sound_path: None,
+            audio_device: None,
+            lock_sound_path: None,
+            music_path: None,
+            tick_mode: Some("boundary".to_string()),
+            boundary_tiebreak: Some("higher".to_string()),
+            ticks_per_revolution: None,
+            tension_ticks: Some(false),
+            tick_sounds: None,
+            max_fps: None,
+            max_frame_dt: Some(0.1),
+            click_to_spin: Some(true),
+            click_hub_only: Some(false),
+            winner_in_center: Some(false),
+            drag_spin: Some(false),
+            friction: Some(0.8),
+            spin_mouse_button: Some("primary".to_string()),
+            reverse_segment_order: Some(false),
+            segment_sort: Some("config".to_string()),
+            start_hint: Some("Press SPACE to spin".to_string()),
+            title_text: None,
+            title_font_size: Some(32.0),
+            pointer_angle_deg: Some(270.0),
+            pointer_positions: None,
+            initial_rotation_deg: None,
+            anchor_first_segment: Some(false),
+            min_slice_degrees: None,
+            segments_csv: None,
+            status_file: None,
+            audit_log: None,
+            webhook_url: None,
+            on_win_command: None,
+            allow_commands: Some(false),
+            export_path: Some("wheel_export.toml".to_string()),
+            segments: vec![
+                SegmentConfig {
+                    label: "1".into(),
+                    weight: 1,
+                    color: None,
+                    font_size: None,
+                    is_blank: None,
+                    tags: None,
+                    image: None,
+                    chain_to: None,
+                    probability: None,
+                },
+                SegmentConfig {
+                    label: "2".into(),
+                    weight: 1,
+                    color: None,
+                    font_size: None,
+                    is_blank: None,
+                    tags: None,
+                    image: None,
+                    chain_to: None,
+                    probability: None,
+                },
+                SegmentConfig {
+                    label: "3".into(),
+                    weight: 1,
+                    color: None,
+                    font_size: None,
+                    is_blank: None,
+                    tags: None,
+                    image: None,
+                    chain_to: None,
+                    probability: None,
+                },
+                SegmentConfig {
+                    label: "4".into(),
+                    weight: 1,
+                    color: None,
+                    font_size: None,
+                    is_blank: None,
+                    tags: None,
+                    image: None,
+                    chain_to: None,
+                    probability: None,
+                },
+                SegmentConfig {
+                    label: "5".into(),
+                    weight: 1,
+                    color: None,
+                    font_size: None,
+                    is_blank: None,
+                    tags: None,
+                    image: None,
+                    chain_to: None,
+                    probability: None,
+                },
+            ],
+            wheels: None,
+            base_dir: None,
+        }
+    }
+}
+
+impl AppConfig {
+    /// Appends the segments from `segments_csv` (if set) to the inline ones,
+    /// likewise for each of the `wheels`. Returns warnings for skipped rows,
+    /// or an error if a file can't be read.
+    pub fn load_external_segments(&mut self) -> Result<Vec<String>, String> {
+        let base_dir = self.base_dir.as_deref();
+        let mut warnings = import_csv(&self.segments_csv, base_dir, &mut self.segments)?;
+        for wheel in self.wheels.iter_mut().flatten() {
+            warnings.extend(import_csv(
+                &wheel.segments_csv,
+                base_dir,
+                &mut wheel.segments,
+            )?);
+        }
+        Ok(warnings)
+    }
+
+    /// One config per entry of `wheels` (or just this one without them): the
+    /// shared settings with that wheel's segments and title. Output files get
+    /// the wheel's number appended so the wheels don't overwrite each other.
+    pub fn wheel_configs(&self) -> Vec<AppConfig> {
+        let Some(wheels) = &self.wheels else {
+            return vec![self.clone()];
+        };
+        wheels
+            .iter()
+            .enumerate()
+            .map(|(i, wheel)| {
+                let numbered =
+                    |path: &Option<String>| path.as_deref().map(|path| numbered_path(path, i + 1));
+                AppConfig {
+                    title_text: wheel.title_text.clone().or_else(|| self.title_text.clone()),
+                    segments: wheel.segments.clone(),
+                    segments_csv: None,
+                    wheels: None,
+                    status_file: numbered(&self.status_file),
+                    export_path: numbered(&self.export_path),
+                    // Wheels with the same sequence would always agree
+                    seed: self.seed.map(|seed| seed.wrapping_add(i as u64)),
+                    // One copy of the music for the whole window
+                    music_path: self.music_path.clone().filter(|_| i == 0),
+                    ..self.clone()
+                }
+            })
+            .collect()
+    }
+
+    // The segments of each wheel, or the single wheel's without `wheels`
+    fn wheel_segments(&self) -> Vec<&[SegmentConfig]> {
+        match &self.wheels {
+            Some(wheels) => wheels.iter().map(|w| w.segments.as_slice()).collect(),
+            None => vec![self.segments.as_slice()],
+        }
+    }
+
+    /// Where an asset path from the config points, relative paths being taken
+    /// from the config file's directory.
+    pub fn asset_path(&self, path: &str) -> PathBuf {
+        resolve_path(self.base_dir.as_deref(), path)
+    }
+
+    /// Image and sound files named by the config, its wheels included, that
+    /// don't exist.
+    pub fn missing_assets(&self) -> Vec<PathBuf> {
+        let sounds = [
+            &self.countdown_sound_path,
+            &self.drumroll_sound_path,
+            &self.start_sound_path,
+            &self.loop_sound_path,
+            &self.lock_sound_path,
+            &self.music_path,
+            &self.fanfare_sound_path,
+        ];
+        let images = [
+            &self.background_image,
+            &self.background_spritesheet,
+            &self.window_icon,
+        ];
+        let segment_images = self
+            .segments
+            .iter()
+            .chain(self.wheels.iter().flatten().flat_map(|w| &w.segments))
+            .filter_map(|s| s.image.as_ref());
+
+        let mut missing: Vec<PathBuf> = sounds
+            .into_iter()
+            .chain(images)
+            .flatten()
+            .chain(self.tick_sounds.iter().flatten())
+            .chain(segment_images)
+            .map(|path| self.asset_path(path))
+            .filter(|path| !path.exists())
+            .collect();
+        missing.sort();
+        missing.dedup();
+        missing
+    }
+
+    // The validation report, as an error when `strict_assets` is set and
+    // asset files are missing: the app mustn't start on fallbacks then
+    pub(crate) fn validate_strict(&self) -> Result<ValidationReport, ValidationReport> {
+        let report = self.validate();
+        if self.strict_assets == Some(true) && !self.missing_assets().is_empty() {
+            Err(report)
+        } else {
+            Ok(report)
+        }
+    }
+
+    /// Parses a config file. With `preset`, the keys of the `[presets.NAME]`
+    /// table of that name replace the base settings of the same name; the
+    /// presets themselves are not part of the resulting config.
+    pub fn from_toml(text: &str, preset: Option<&str>) -> Result<AppConfig, String> {
+        let mut table: toml::Table = toml::from_str(text).map_err(|e| e.to_string())?;
+        let presets = table.remove("presets");
+        let presets = presets.as_ref().and_then(toml::Value::as_table);
+
+        if let Some(name) = preset {
+            let Some(overrides) = presets.and_then(|p| p.get(name)).and_then(|p| p.as_table())
+            else {
+                let known: Vec<&str> = presets
+                    .into_iter()
+                    .flat_map(|p| p.keys())
+                    .map(String::as_str)
+                    .collect();
+                return Err(if known.is_empty() {
+                    format!("unknown preset '{name}', the config defines no presets")
+                } else {
+                    format!(
+                        "unknown preset '{name}', expected one of {}",
+                        known.join(", ")
+                    )
+                });
+            };
+            for (key, value) in overrides {
+                table.insert(key.clone(), value.clone());
+            }
+        }
+
+        toml::Value::Table(table)
+            .try_into()
+            .map_err(|e| e.to_string())
+    }
+
+    /// Serializes the config to TOML. The inline segments are taken to be the
+    /// complete list, so `segments_csv` is left out to avoid importing it twice.
+    pub fn to_toml(&self) -> Result<String, String> {
+        let config = AppConfig {
+            segments_csv: None,
+            ..self.clone()
+        };
+        toml::to_string(&config).map_err(|e| format!("failed to serialize config: {e}"))
+    }
+}
+
+// Rounding slack allowed when probabilities are meant to add up to exactly 100%
+const PROBABILITY_TOLERANCE: f32 = 0.01;
+// Internal weight units per percent of probability
+const PROBABILITY_SCALE: f32 = 1000.0;
+
+// Turns the `probability` of segments into weights on a common scale with the
+// weighted segments, which split the remaining percentage between them. The
+// weights are then reduced by their common divisor, so 25% and 75% weigh 1
+// and 3. Without any probabilities the weights are left as they are.
+pub(crate) fn apply_probabilities(segments: &mut [SegmentConfig]) {
+    if segments.iter().all(|s| s.probability.is_none()) {
+        return;
+    }
+    let fixed: f32 = segments.iter().filter_map(|s| s.probability).sum();
+    let remainder = (100.0 - fixed).max(0.0);
+    let weighted: u32 = segments
+        .iter()
+        .filter(|s| s.probability.is_none())
+        .map(|s| s.weight)
+        .sum();
+
+    for seg in segments.iter_mut() {
+        let percent = match seg.probability {
+            Some(probability) => probability.clamp(0.0, 100.0),
+            None if weighted > 0 => remainder * seg.weight as f32 / weighted as f32,
+            None => 0.0,
+        };
+        seg.weight = (percent * PROBABILITY_SCALE).round() as u32;
+    }
+
+    let divisor = segments.iter().fold(0, |d, s| gcd(d, s.weight));
+    if divisor > 1 {
+        segments.iter_mut().for_each(|s| s.weight /= divisor);
+    }
+}
+
+// --- VALIDATION ---
+
+/// Problems found in an [`AppConfig`]. Warnings are recoverable (the app falls
+/// back to a default), errors make the wheel unusable.
+#[derive(Debug, Default)]
+pub struct ValidationReport {
+    pub warnings: Vec<String>,
+    pub errors: Vec<String>,
+}
+
+impl ValidationReport {
+    pub fn is_ok(&self) -> bool {
+        self.errors.is_empty()
+    }
+
+    pub(crate) fn print(&self) {
+        for warning in &self.warnings {
+            eprintln!("warning: {warning}");
+        }
+        for error in &self.errors {
+            eprintln!("error: {error}");
+        }
+    }
+}
+
+impl AppConfig {
+    pub fn validate(&self) -> ValidationReport {
+        let mut report = ValidationReport::default();
+
+        match &self.wheels {
+            Some(wheels) if wheels.is_empty() => {
+                report.errors.push("wheels is empty".to_string());
+            }
+            Some(wheels) => {
+                for wheel in wheels {
+                    validate_segments(&wheel.segments, &mut report);
+                }
+                let names: Vec<&str> = wheels.iter().filter_map(|w| w.name.as_deref()).collect();
+                for target in wheels
+                    .iter()
+                    .flat_map(|w| &w.segments)
+                    .filter_map(|s| s.chain_to.as_deref())
+                {
+                    if !names.contains(&target) {
+                        report.warnings.push(format!(
+                            "chain_to '{target}' names no wheel, the segment doesn't chain"
+                        ));
+                    }
+                }
+            }
+            None => {
+                validate_segments(&self.segments, &mut report);
+                if self.segments.iter().any(|s| s.chain_to.is_some()) {
+                    report
+                        .warnings
+                        .push("chain_to only works between wheels and is ignored".to_string());
+                }
+            }
+        }
+
+        if !(self.spin_duration_ms.is_finite() && self.spin_duration_ms > 0.0) {
+            report.errors.push(format!(
+                "spin_duration_ms must be positive, got {}",
+                self.spin_duration_ms
+            ));
+        }
+
+        for (name, color) in [
+            ("center_color", &self.center_color),
+            ("rim_color", &self.rim_color),
+            ("label_bg_color", &self.label_bg_color),
+            ("winner_card_color", &self.winner_card_color),
+            ("winner_border_color", &self.winner_border_color),
+        ] {
+            if let Some(color) = color
+                && parse_hex_color(color).is_none()
+            {
+                report
+                    .warnings
+                    .push(format!("{name} '{color}' is not a valid hex color"));
+            }
+        }
+
+        for (name, delay) in [
+            ("confetti_delay_ms", self.confetti_delay_ms),
+            ("fanfare_delay_ms", self.fanfare_delay_ms),
+        ] {
+            if let Some(delay) = delay
+                && !(delay.is_finite() && delay >= 0.0)
+            {
+                report
+                    .warnings
+                    .push(format!("{name} {delay} is negative, it fires immediately"));
+            }
+        }
+
+        if let Some(max) = self.max_spin_seconds
+            && !(max.is_finite() && max > 0.0)
+        {
+            report.warnings.push(format!(
+                "max_spin_seconds {max} is not positive and is ignored"
+            ));
+        }
+
+        if let Some(tilt) = self.tilt
+            && !(0.0..=MAX_TILT_DEGREES).contains(&tilt)
+        {
+            report.warnings.push(format!(
+                "tilt {tilt} is outside 0-{MAX_TILT_DEGREES} degrees and will be clamped"
+            ));
+        }
+
+        if let Some(gap) = self.segment_gap_degrees
+            && !(gap.is_finite() && gap >= 0.0)
+        {
+            report.warnings.push(format!(
+                "segment_gap_degrees {gap} is negative and is ignored"
+            ));
+        }
+
+        if let Some(min_degrees) = self.min_slice_degrees {
+            for segments in self.wheel_segments() {
+                if !segments.is_empty()
+                    && !(0.0..=360.0 / segments.len() as f32).contains(&min_degrees)
+                {
+                    report.warnings.push(format!(
+                        "min_slice_degrees {min_degrees} does not fit {} segments and will be clamped",
+                        segments.len()
+                    ));
+                }
+            }
+        }
+
+        if self.strict_assets == Some(true) {
+            for path in self.missing_assets() {
+                report
+                    .errors
+                    .push(format!("missing asset '{}'", path.display()));
+            }
+        }
+
+        if self.max_spins_allowed == Some(0) {
+            report
+                .warnings
+                .push("max_spins_allowed is 0, the wheel can never spin".to_string());
+        }
+
+        if let Some(delay) = self.auto_spin_after_ms
+            && !(delay.is_finite() && delay >= 0.0)
+        {
+            report.warnings.push(format!(
+                "auto_spin_after_ms {delay} is negative and is ignored"
+            ));
+        }
+
+        if let Some(cooldown) = self.spin_cooldown_ms
+            && !(cooldown.is_finite() && cooldown >= 0.0)
+        {
+            report.warnings.push(format!(
+                "spin_cooldown_ms {cooldown} is negative and is ignored"
+            ));
+        }
+
+        if let Some(interval) = self.auto_spin_interval_ms
+            && !(interval.is_finite() && interval > 0.0)
+        {
+            report.warnings.push(format!(
+                "auto_spin_interval_ms {interval} must be positive and is ignored"
+            ));
+        }
+
+        if self.winners_per_spin == Some(0) {
+            report
+                .warnings
+                .push("winners_per_spin is 0, one winner is drawn".to_string());
+        } else if let Some(count) = self.winners_per_spin {
+            for segments in self.wheel_segments() {
+                let drawable = segments.iter().filter(|s| can_win(s)).count();
+                if count > drawable {
+                    report.warnings.push(format!(
+                        "winners_per_spin {count} exceeds the {drawable} segments that can win"
+                    ));
+                }
+            }
+        }
+
+        if self.on_win_command.is_some() && self.allow_commands != Some(true) {
+            report
+                .warnings
+                .push("on_win_command is ignored unless allow_commands is set to true".to_string());
+        }
+
+        if self.webhook_url.is_some() && !cfg!(feature = "webhook") {
+            report.warnings.push(
+                "webhook_url is set but rheel was built without the webhook feature".to_string(),
+            );
+        }
+
+        if let Some(fps) = self.max_fps
+            && !(fps.is_finite() && fps > 0.0)
+        {
+            report
+                .warnings
+                .push(format!("max_fps {fps} must be positive and is ignored"));
+        }
+
+        if let Some(dt) = self.max_frame_dt
+            && !(dt.is_finite() && dt > 0.0)
+        {
+            report.warnings.push(format!(
+                "max_frame_dt {dt} must be positive, the default is used"
+            ));
+        }
+
+        if let Some(points) = self.easing_bezier
+            && !is_valid_bezier(points)
+        {
+            report.warnings.push(format!(
+                "easing_bezier {points:?} needs finite points with x1 and x2 in 0..=1, the default easing is used"
+            ));
+        }
+
+        if let Some(friction) = self.friction
+            && !(friction.is_finite() && friction >= 0.0)
+        {
+            report.warnings.push(format!(
+                "friction {friction} must not be negative, the default is used"
+            ));
+        }
+
+        if let Some(ratio) = self.center_radius_ratio
+            && !(0.0..=0.8).contains(&ratio)
+        {
+            report.warnings.push(format!(
+                "center_radius_ratio {ratio} is outside 0.0..=0.8 and will be clamped"
+            ));
+        }
+
+        for (name, size) in [
+            ("winner_font_size", self.winner_font_size),
+            ("label_font_size", self.label_font_size),
+            ("title_font_size", self.title_font_size),
+        ] {
+            if let Some(size) = size
+                && size < 0.0
+            {
+                report.warnings.push(format!("{name} {size} is negative"));
+            }
+        }
+
+        if let Some(format) = &self.label_number_format
+            && !format.contains("{n")
+        {
+            report.warnings.push(format!(
+                "label_number_format '{format}' has no {{n}} placeholder and is ignored"
+            ));
+        }
+
+        if let Some(layout) = &self.layout
+            && parse_layout(layout).is_none()
+        {
+            report.warnings.push(format!(
+                "unknown layout '{layout}', expected wheel, reel or picker"
+            ));
+        }
+
+        if let Some(direction) = &self.label_direction
+            && parse_label_direction(direction).is_none()
+        {
+            report.warnings.push(format!(
+                "unknown label_direction '{direction}', expected ltr, rtl or vertical"
+            ));
+        }
+
+        if let Some(sort) = &self.segment_sort
+            && parse_segment_sort(sort).is_none()
+        {
+            report.warnings.push(format!(
+                "unknown segment_sort '{sort}', expected config, label, weight_asc or weight_desc"
+            ));
+        }
+
+        if let Some(width) = self.label_wrap_width
+            && !(width.is_finite() && width > 0.0)
+        {
+            report.warnings.push(format!(
+                "label_wrap_width {width} is not positive and is ignored"
+            ));
+        }
+
+        if self.label_max_chars == Some(0) {
+            report
+                .warnings
+                .push("label_max_chars is 0 and is ignored".to_string());
+        }
+
+        if self.ticks_per_revolution == Some(0) {
+            report
+                .warnings
+                .push("ticks_per_revolution is 0 and is ignored".to_string());
+        }
+
+        if let Some(format) = &self.odds_format
+            && parse_odds_format(format).is_none()
+        {
+            report.warnings.push(format!(
+                "unknown odds_format '{format}', expected percent, fraction or ratio"
+            ));
+        }
+
+        if let Some(policy) = &self.boundary_tiebreak
+            && parse_boundary_tiebreak(policy).is_none()
+        {
+            report.warnings.push(format!(
+                "unknown boundary_tiebreak '{policy}', expected lower, higher or random"
+            ));
+        }
+
+        if let Some(mode) = &self.tick_mode
+            && parse_tick_mode(mode).is_none()
+        {
+            report.warnings.push(format!(
+                "unknown tick_mode '{mode}', expected boundary or velocity"
+            ));
+        }
+
+        if let Some(button) = &self.spin_mouse_button
+            && parse_mouse_button(button).is_none()
+        {
+            report.warnings.push(format!(
+                "unknown spin_mouse_button '{button}', expected primary, secondary or middle"
+            ));
+        }
+
+        if let Some(scale) = self.ui_scale
+            && !(MIN_UI_SCALE..=MAX_UI_SCALE).contains(&scale)
+        {
+            report.warnings.push(format!(
+                "ui_scale {scale} is outside {MIN_UI_SCALE}..={MAX_UI_SCALE} and will be clamped"
+            ));
+        }
+
+        if let Some(position) = &self.winner_message_position
+            && parse_winner_position(position).is_none()
+        {
+            report.warnings.push(format!(
+                "unknown winner_message_position '{position}', expected center, top, bottom or below_wheel"
+            ));
+        }
+
+        if let Some(theme) = &self.ui_theme
+            && parse_theme(theme).is_none()
+        {
+            report.warnings.push(format!(
+                "unknown ui_theme '{theme}', expected dark or light"
+            ));
+        }
+
+        if self.winner_messages.as_ref().is_some_and(|m| m.is_empty()) {
+            report
+                .warnings
+                .push("winner_messages is empty, winner_message is used instead".to_string());
+        }
+
+        report
+    }
+}
+
+// Whether a segment has a chance of winning, by weight or by probability
+fn can_win(segment: &SegmentConfig) -> bool {
+    segment.weight > 0 || segment.probability.is_some_and(|p| p > 0.0)
+}
+
+fn validate_segments(segments: &[SegmentConfig], report: &mut ValidationReport) {
+    if segments.is_empty() {
+        report.errors.push("no segments defined".to_string());
+    } else if !segments.iter().any(can_win) {
+        report.errors.push("all segment weights are 0".to_string());
+    }
+
+    let total_probability: f32 = segments.iter().filter_map(|s| s.probability).sum();
+    if total_probability > 100.0 + PROBABILITY_TOLERANCE {
+        report.errors.push(format!(
+            "segment probabilities add up to {total_probability}%, more than 100%"
+        ));
+    }
+
+    for seg in segments {
+        if let Some(probability) = seg.probability {
+            if seg.weight != 0 {
+                report.errors.push(format!(
+                    "segment '{}' sets both weight and probability",
+                    seg.label
+                ));
+            }
+            if !(0.0..=100.0).contains(&probability) {
+                report.errors.push(format!(
+                    "segment '{}' has probability {probability}, expected 0 to 100",
+                    seg.label
+                ));
+            }
+        } else if seg.weight == 0 {
+            report.warnings.push(format!(
+                "segment '{}' has weight 0 and can never win",
+                seg.label
+            ));
+        }
+        if let Some(size) = seg.font_size
+            && size < 0.0
+        {
+            report.warnings.push(format!(
+                "segment '{}' has negative font_size {size}",
+                seg.label
+            ));
+        }
+        if let Some(color) = &seg.color
+            && parse_hex_color(color).is_none()
+        {
+            report.warnings.push(format!(
+                "segment '{}' has invalid color '{}', a generated color is used",
+                seg.label, color
+            ));
+        }
+    }
+}
+
+// --- BUILDER ---
+
+/// Chainable construction of an [`AppConfig`] for embedders. Starts from the
+/// defaults without any segments; anything not covered by a method can be set
+/// through [`AppConfigBuilder::with`].
+///
+/// ```
+/// let config = rheel::AppConfigBuilder::new()
+///     .spin_duration_ms(3000.0)
+///     .add_segment("Pizza", 2, Some("#E74C3C"))
+///     .add_segment("Tacos", 1, None)
+///     .seed(42)
+///     .build()
+///     .expect("valid config");
+/// assert_eq!(config.segments.len(), 2);
+/// ```
+#[derive(Clone, Debug)]
+pub struct AppConfigBuilder {
+    config: AppConfig,
+}
+
+impl Default for AppConfigBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl AppConfigBuilder {
+    pub fn new() -> Self {
+        Self {
+            config: AppConfig {
+                segments: Vec::new(),
+                ..AppConfig::default()
+            },
+        }
+    }
+
+    /// `color` is a hex string such as `"#FF8800"`, `None` generates one from the label
+    pub fn add_segment(mut self, label: &str, weight: u32, color: Option<&str>) -> Self {
+        self.config.segments.push(SegmentConfig {
+            label: label.to_string(),
+            weight,
+            color: color.map(str::to_string),
+            font_size: None,
+            is_blank: None,
+            tags: None,
+            image: None,
+            chain_to: None,
+            probability: None,
+        });
+        self
+    }
+
+    pub fn add_segment_config(mut self, segment: SegmentConfig) -> Self {
+        self.config.segments.push(segment);
+        self
+    }
+
+    pub fn layout(mut self, layout: &str) -> Self {
+        self.config.layout = Some(layout.to_string());
+        self
+    }
+
+    pub fn spin_duration_ms(mut self, ms: f32) -> Self {
+        self.config.spin_duration_ms = ms;
+        self
+    }
+
+    pub fn title_text(mut self, title: &str) -> Self {
+        self.config.title_text = Some(title.to_string());
+        self
+    }
+
+    pub fn winners_per_spin(mut self, count: usize) -> Self {
+        self.config.winners_per_spin = Some(count);
+        self
+    }
+
+    pub fn fair_bag(mut self, enabled: bool) -> Self {
+        self.config.fair_bag = Some(enabled);
+        self
+    }
+
+    pub fn elimination_mode(mut self, enabled: bool) -> Self {
+        self.config.elimination_mode = Some(enabled);
+        self
+    }
+
+    pub fn max_spins_allowed(mut self, spins: u32) -> Self {
+        self.config.max_spins_allowed = Some(spins);
+        self
+    }
+
+    pub fn auto_spin_interval_ms(mut self, ms: f32) -> Self {
+        self.config.auto_spin_interval_ms = Some(ms);
+        self
+    }
+
+    pub fn reduced_motion(mut self, enabled: bool) -> Self {
+        self.config.reduced_motion = Some(enabled);
+        self
+    }
+
+    /// Makes the spins reproducible, the same seed giving the same results
+    pub fn seed(mut self, seed: u64) -> Self {
+        self.config.seed = Some(seed);
+        self
+    }
+
+    /// Escape hatch for the options without a dedicated method
+    pub fn with(mut self, f: impl FnOnce(&mut AppConfig)) -> Self {
+        f(&mut self.config);
+        self
+    }
+
+    /// The config, or the validation report if it has errors. Warnings alone
+    /// don't fail the build, the app falls back to defaults for those.
+    pub fn build(self) -> Result<AppConfig, ValidationReport> {
+        let report = self.config.validate();
+        if report.is_ok() {
+            Ok(self.config)
+        } else {
+            Err(report)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn builder_builds_a_valid_config() {
+        let config = AppConfigBuilder::new()
+            .add_segment("Pizza", 2, Some("#E74C3C"))
+            .add_segment("Tacos", 1, None)
+            .spin_duration_ms(3000.0)
+            .winners_per_spin(2)
+            .seed(9)
+            .build()
+            .expect("valid config");
+
+        assert_eq!(config.segments.len(), 2);
+        assert_eq!(config.segments[0].weight, 2);
+        assert_eq!(config.segments[0].color.as_deref(), Some("#E74C3C"));
+        assert_eq!(config.spin_duration_ms, 3000.0);
+        assert_eq!(config.winners_per_spin, Some(2));
+        assert_eq!(config.seed, Some(9));
+    }
+
+    #[test]
+    fn builder_rejects_a_config_without_segments() {
+        let report = AppConfigBuilder::new().build().unwrap_err();
+        assert!(report.errors.iter().any(|e| e.contains("no segments")));
+    }
+
+    #[test]
+    fn builder_rejects_a_negative_spin_duration() {
+        let report = AppConfigBuilder::new()
+            .add_segment("Only", 1, None)
+            .spin_duration_ms(-5.0)
+            .build()
+            .unwrap_err();
+        assert!(report.errors.iter().any(|e| e.contains("spin_duration_ms")));
+    }
+
+    fn segment(label: &str, weight: u32, probability: Option<f32>) -> SegmentConfig {
+        SegmentConfig {
+            label: label.to_string(),
+            weight,
+            color: None,
+            font_size: None,
+            is_blank: None,
+            tags: None,
+            image: None,
+            chain_to: None,
+            probability,
+        }
+    }
+
+    fn weights_after_probabilities(segments: &[(u32, Option<f32>)]) -> Vec<u32> {
+        let mut segments: Vec<SegmentConfig> = segments
+            .iter()
+            .map(|&(weight, probability)| segment("Segment", weight, probability))
+            .collect();
+        apply_probabilities(&mut segments);
+        segments.iter().map(|s| s.weight).collect()
+    }
+
+    #[test]
+    fn probabilities_become_reduced_weights() {
+        assert_eq!(
+            weights_after_probabilities(&[(0, Some(25.0)), (0, Some(75.0))]),
+            [1, 3]
+        );
+        assert_eq!(
+            weights_after_probabilities(&[(0, Some(12.5)), (0, Some(87.5))]),
+            [1, 7]
+        );
+        assert_eq!(
+            weights_after_probabilities(&[(0, Some(33.3)), (0, Some(66.7))]),
+            [333, 667]
+        );
+    }
+
+    #[test]
+    fn weighted_segments_split_the_remaining_probability() {
+        // 50% fixed, the other 50% split 1:3 between the weighted segments
+        assert_eq!(
+            weights_after_probabilities(&[(0, Some(50.0)), (1, None), (3, None)]),
+            [4, 1, 3]
+        );
+        // Nothing left over for the weighted segment
+        assert_eq!(
+            weights_after_probabilities(&[(0, Some(100.0)), (5, None)]),
+            [1, 0]
+        );
+    }
+
+    #[test]
+    fn weights_without_probabilities_are_untouched() {
+        assert_eq!(weights_after_probabilities(&[(2, None), (4, None)]), [2, 4]);
+    }
+
+    #[test]
+    fn probabilities_over_100_percent_are_rejected() {
+        let report = AppConfigBuilder::new()
+            .add_segment_config(segment("Most", 0, Some(70.0)))
+            .add_segment_config(segment("More", 0, Some(40.0)))
+            .build()
+            .unwrap_err();
+        assert!(report.errors.iter().any(|e| e.contains("more than 100%")));
+    }
+
+    #[test]
+    fn probability_segments_count_towards_winners_per_spin() {
+        let config = AppConfigBuilder::new()
+            .add_segment_config(segment("Heads", 0, Some(50.0)))
+            .add_segment_config(segment("Tails", 0, Some(50.0)))
+            .winners_per_spin(2)
+            .build()
+            .expect("valid config");
+
+        let report = config.validate();
+        assert!(
+            !report
+                .warnings
+                .iter()
+                .any(|w| w.contains("winners_per_spin")),
+            "{:?}",
+            report.warnings
+        );
+    }
+
+    #[test]
+    fn winners_per_spin_is_checked_per_wheel() {
+        let wheel = |segments: Vec<SegmentConfig>| WheelConfig {
+            name: None,
+            title_text: None,
+            segments_csv: None,
+            segments,
+        };
+        let config = AppConfig {
+            winners_per_spin: Some(2),
+            wheels: Some(vec![
+                wheel(vec![segment("A", 1, None), segment("B", 0, Some(40.0))]),
+                wheel(vec![segment("Only", 1, None)]),
+            ]),
+            ..AppConfig::default()
+        };
+
+        let report = config.validate();
+        let warnings: Vec<_> = report
+            .warnings
+            .iter()
+            .filter(|w| w.contains("winners_per_spin"))
+            .collect();
+        assert_eq!(warnings.len(), 1, "{warnings:?}");
+        assert!(warnings[0].contains("the 1 segments"));
+    }
+
+    #[test]
+    fn relative_paths_resolve_against_the_config_directory() {
+        let base = Path::new("/srv/wheels");
+        assert_eq!(
+            resolve_path(Some(base), "sounds/click.wav"),
+            Path::new("/srv/wheels/sounds/click.wav")
+        );
+        assert_eq!(
+            resolve_path(None, "sounds/click.wav"),
+            Path::new("sounds/click.wav")
+        );
+    }
+
+    #[test]
+    fn absolute_paths_ignore_the_config_directory() {
+        let absolute = std::env::temp_dir().join("click.wav");
+        let absolute = absolute.to_str().expect("utf-8 temp dir");
+        assert_eq!(
+            resolve_path(Some(Path::new("/srv/wheels")), absolute),
+            Path::new(absolute)
+        );
+
+        let config = AppConfig {
+            base_dir: Some("/srv/wheels".into()),
+            ..AppConfig::default()
+        };
+        assert_eq!(config.asset_path(absolute), Path::new(absolute));
+        assert_eq!(config.asset_path("bg.png"), Path::new("/srv/wheels/bg.png"));
+    }
+}
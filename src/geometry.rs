@@ -0,0 +1,222 @@
+use std::f32::consts::PI;
+
+// --- WHEEL GEOMETRY ---
+
+/// Angular extent of one segment, stored as cumulative fractions of a full
+/// turn measured clockwise from the wheel's current rotation.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct SliceBounds {
+    /// Index of the segment in the config order
+    pub index: usize,
+    pub start: f32,
+    pub end: f32,
+}
+
+impl SliceBounds {
+    /// Width of the slice in radians
+    pub fn width(&self) -> f32 {
+        (self.end - self.start) * 2.0 * PI
+    }
+
+    /// Screen angle (radians) where the slice begins for a given wheel rotation
+    pub fn start_angle(&self, rotation: f32) -> f32 {
+        rotation + self.start * 2.0 * PI
+    }
+
+    pub fn mid_angle(&self, rotation: f32) -> f32 {
+        rotation + (self.start + self.end) * PI
+    }
+}
+
+/// Slice boundaries computed once from the segment weights and shared by the
+/// drawing code and the hit-test, so the two can't disagree.
+#[derive(Clone, Debug, Default)]
+pub struct WheelGeometry {
+    slices: Vec<SliceBounds>,
+}
+
+impl WheelGeometry {
+    /// Lays the segments out clockwise in `order`, proportionally to `weights`.
+    /// Segments missing from `order` are left off the wheel entirely.
+    /// Slices narrower than `min_fraction` of a turn are widened to it, taking
+    /// the space from the other slices in proportion to their weights.
+    pub fn new(weights: &[u32], order: impl IntoIterator<Item = usize>, min_fraction: f32) -> Self {
+        let order: Vec<usize> = order.into_iter().collect();
+        let ordered_weights: Vec<u32> = order.iter().map(|&i| weights[i]).collect();
+        let fractions = layout_fractions(&ordered_weights, min_fraction);
+
+        let mut cursor = 0.0;
+        let slices = order
+            .into_iter()
+            .zip(fractions)
+            .map(|(index, fraction)| {
+                let start = cursor;
+                cursor += fraction;
+                SliceBounds {
+                    index,
+                    start,
+                    end: cursor,
+                }
+            })
+            .collect();
+
+        Self { slices }
+    }
+
+    /// Slices in clockwise drawing order
+    pub fn slices(&self) -> &[SliceBounds] {
+        &self.slices
+    }
+
+    pub fn slice(&self, index: usize) -> Option<&SliceBounds> {
+        self.slices.iter().find(|s| s.index == index)
+    }
+
+    /// Index of the segment lying at screen angle `angle` when the wheel is
+    /// turned by `rotation`. Rounding gaps at the end of the turn resolve to
+    /// the last slice.
+    pub fn segment_at(&self, rotation: f32, angle: f32) -> Option<usize> {
+        let fraction = (angle - rotation).rem_euclid(2.0 * PI) / (2.0 * PI);
+
+        self.slices
+            .iter()
+            .find(|s| fraction >= s.start && fraction < s.end)
+            .or(self.slices.last())
+            .map(|s| s.index)
+    }
+
+    /// The two segments meeting at screen angle `angle`, the earlier one in
+    /// clockwise order first, if the angle lies on a boundary between slices
+    /// (up to floating point noise).
+    pub fn boundary_at(&self, rotation: f32, angle: f32) -> Option<(usize, usize)> {
+        const EPSILON: f32 = 1e-6;
+        if self.slices.len() < 2 {
+            return None;
+        }
+        let fraction = (angle - rotation).rem_euclid(2.0 * PI) / (2.0 * PI);
+
+        let i = self.slices.iter().position(|s| {
+            (fraction - s.start).abs() < EPSILON || (fraction - 1.0 - s.start).abs() < EPSILON
+        })?;
+        let lower = self.slices[(i + self.slices.len() - 1) % self.slices.len()];
+        Some((lower.index, self.slices[i].index))
+    }
+}
+
+fn layout_fractions(weights: &[u32], min_fraction: f32) -> Vec<f32> {
+    let min_fraction = min_fraction.clamp(0.0, 1.0 / weights.len().max(1) as f32);
+    let mut pinned = vec![false; weights.len()];
+
+    loop {
+        let pinned_count = pinned.iter().filter(|p| **p).count();
+        let free_weight: u32 = weights
+            .iter()
+            .zip(&pinned)
+            .filter(|(_, p)| !**p)
+            .map(|(w, _)| *w)
+            .sum();
+        let free_fraction = 1.0 - pinned_count as f32 * min_fraction;
+
+        let fractions: Vec<f32> = weights
+            .iter()
+            .zip(&pinned)
+            .map(|(w, p)| {
+                if *p {
+                    min_fraction
+                } else if free_weight == 0 {
+                    0.0
+                } else {
+                    free_fraction * *w as f32 / free_weight as f32
+                }
+            })
+            .collect();
+
+        let mut changed = false;
+        for (i, fraction) in fractions.iter().enumerate() {
+            if !pinned[i] && *fraction < min_fraction {
+                pinned[i] = true;
+                changed = true;
+            }
+        }
+
+        if !changed {
+            return fractions;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EPSILON: f32 = 1e-6;
+
+    fn bounds(geometry: &WheelGeometry) -> Vec<(usize, f32, f32)> {
+        geometry
+            .slices()
+            .iter()
+            .map(|s| (s.index, s.start, s.end))
+            .collect()
+    }
+
+    fn assert_bounds(geometry: &WheelGeometry, expected: &[(usize, f32, f32)]) {
+        let actual = bounds(geometry);
+        assert_eq!(actual.len(), expected.len(), "{actual:?}");
+        for (a, e) in actual.iter().zip(expected) {
+            assert_eq!(a.0, e.0, "{actual:?}");
+            assert!(
+                (a.1 - e.1).abs() < EPSILON && (a.2 - e.2).abs() < EPSILON,
+                "{actual:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn slices_are_cumulative_fractions_of_the_weights() {
+        let geometry = WheelGeometry::new(&[1, 2, 1], 0..3, 0.0);
+        assert_bounds(
+            &geometry,
+            &[(0, 0.0, 0.25), (1, 0.25, 0.75), (2, 0.75, 1.0)],
+        );
+    }
+
+    #[test]
+    fn order_lays_slices_out_and_skips_missing_segments() {
+        let geometry = WheelGeometry::new(&[1, 2, 1], [2, 0], 0.0);
+        assert_bounds(&geometry, &[(2, 0.0, 0.5), (0, 0.5, 1.0)]);
+        assert!(geometry.slice(1).is_none());
+    }
+
+    #[test]
+    fn segment_at_and_boundary_at_follow_the_rotation() {
+        let geometry = WheelGeometry::new(&[1, 1, 1, 1], 0..4, 0.0);
+        let rotation = PI / 4.0;
+        assert_eq!(geometry.segment_at(rotation, rotation + 0.1), Some(0));
+        assert_eq!(geometry.segment_at(rotation, rotation - 0.1), Some(3));
+        assert_eq!(
+            geometry.boundary_at(rotation, rotation + PI / 2.0),
+            Some((0, 1))
+        );
+        assert_eq!(geometry.boundary_at(rotation, rotation), Some((3, 0)));
+        assert_eq!(geometry.boundary_at(rotation, rotation + 0.1), None);
+    }
+
+    #[test]
+    fn min_fraction_pins_narrow_slices() {
+        let geometry = WheelGeometry::new(&[1, 98, 1], 0..3, 0.1);
+        assert_bounds(&geometry, &[(0, 0.0, 0.1), (1, 0.1, 0.9), (2, 0.9, 1.0)]);
+    }
+
+    #[test]
+    fn min_fraction_is_capped_at_an_equal_split() {
+        let geometry = WheelGeometry::new(&[1, 3], 0..2, 0.9);
+        assert_bounds(&geometry, &[(0, 0.0, 0.5), (1, 0.5, 1.0)]);
+    }
+
+    #[test]
+    fn empty_geometry_has_no_segment() {
+        let geometry = WheelGeometry::new(&[], std::iter::empty(), 0.0);
+        assert_eq!(geometry.segment_at(0.0, 0.0), None);
+        assert_eq!(geometry.boundary_at(0.0, 0.0), None);
+    }
+}
@@ -0,0 +1,439 @@
+use crate::app::{
+    BoundaryTiebreak, LabelDirection, Layout, OddsFormat, SegmentSort, TickMode, WinnerPosition,
+};
+use eframe::egui;
+use rand::{Rng, SeedableRng};
+use std::borrow::Cow;
+use std::collections::hash_map::DefaultHasher;
+use std::f32::consts::PI;
+use std::hash::{Hash, Hasher};
+
+// --- HELPERS ---
+
+pub(crate) fn deg_to_rad(degrees: f32) -> f32 {
+    degrees / 180.0 * PI
+}
+
+pub(crate) fn pointer_triangle(
+    center: egui::Pos2,
+    outer_radius: f32,
+    angle: f32,
+) -> Vec<egui::Pos2> {
+    let dir = egui::vec2(angle.cos(), angle.sin());
+    let side = egui::vec2(-dir.y, dir.x);
+    let base = center + dir * (outer_radius + 20.0);
+    vec![
+        base - side * 15.0,
+        base + side * 15.0,
+        center + dir * (outer_radius - 10.0),
+    ]
+}
+
+// Outline of a pie slice starting at `start` radians and `width` radians wide,
+// its arc made of `steps_per_radian` straight pieces per radian
+pub(crate) fn pie_slice_points(
+    center: egui::Pos2,
+    radius: f32,
+    start: f32,
+    width: f32,
+    steps_per_radian: f32,
+) -> Vec<egui::Pos2> {
+    let steps = (width * steps_per_radian).max(3.0) as usize;
+    let mut points = vec![center];
+    for i in 0..=steps {
+        let a = start + (i as f32 / steps as f32) * width;
+        points.push(center + egui::vec2(a.cos(), a.sin()) * radius);
+    }
+    points
+}
+
+// A texture cropped to a circle, the image's center on `center`
+pub(crate) fn circle_image_mesh(
+    texture: egui::TextureId,
+    center: egui::Pos2,
+    radius: f32,
+) -> egui::Mesh {
+    let steps = 64;
+    let mut mesh = egui::Mesh::with_texture(texture);
+    mesh.vertices.push(egui::epaint::Vertex {
+        pos: center,
+        uv: egui::pos2(0.5, 0.5),
+        color: egui::Color32::WHITE,
+    });
+    for i in 0..=steps {
+        let dir = egui::Vec2::angled(i as f32 / steps as f32 * std::f32::consts::TAU);
+        mesh.vertices.push(egui::epaint::Vertex {
+            pos: center + dir * radius,
+            uv: egui::pos2(0.5, 0.5) + dir * 0.5,
+            color: egui::Color32::WHITE,
+        });
+        if i > 0 {
+            mesh.add_triangle(0, i, i + 1);
+        }
+    }
+    mesh
+}
+
+// Spacing of the lines and dots in slice patterns, in points
+const PATTERN_SPACING: f32 = 14.0;
+
+// Pattern number `pattern` (cycling through plain, rings, spokes, dots and a
+// grid of rings and spokes) clipped to the ring between `inner` and `outer`
+// of a pie slice starting at `start` radians and `width` radians wide
+pub(crate) fn slice_pattern_shapes(
+    pattern: usize,
+    center: egui::Pos2,
+    inner: f32,
+    outer: f32,
+    start: f32,
+    width: f32,
+    color: egui::Color32,
+) -> Vec<egui::Shape> {
+    let stroke = egui::Stroke::new(1.5, color);
+    let at = |r: f32, a: f32| center + egui::vec2(a.cos(), a.sin()) * r;
+    let radii = || {
+        (1..)
+            .map(move |i| inner + i as f32 * PATTERN_SPACING)
+            .take_while(move |r| *r < outer - PATTERN_SPACING * 0.5)
+    };
+
+    let rings = || {
+        radii().map(move |r| {
+            let steps = (width * 15.0).max(3.0) as usize;
+            let points = (0..=steps)
+                .map(|i| at(r, start + i as f32 / steps as f32 * width))
+                .collect();
+            egui::Shape::line(points, stroke)
+        })
+    };
+    let spokes = || {
+        let count = (width * (inner + outer) * 0.5 / PATTERN_SPACING).floor() as usize;
+        (1..count).map(move |k| {
+            let a = start + k as f32 / count as f32 * width;
+            egui::Shape::line_segment([at(inner, a), at(outer, a)], stroke)
+        })
+    };
+    let dots = || {
+        radii().flat_map(move |r| {
+            let count = (width * r / PATTERN_SPACING).floor() as usize;
+            (0..count).map(move |k| {
+                let a = start + (k as f32 + 0.5) / count as f32 * width;
+                egui::Shape::circle_filled(at(r, a), 2.0, color)
+            })
+        })
+    };
+
+    match pattern % 5 {
+        1 => rings().collect(),
+        2 => spokes().collect(),
+        3 => dots().collect(),
+        4 => rings().chain(spokes()).collect(),
+        _ => Vec::new(),
+    }
+}
+
+pub(crate) fn hsv_to_rgb(h: f32, s: f32, v: f32) -> egui::Color32 {
+    let c = v * s;
+    let x = c * (1.0 - ((h / 60.0) % 2.0 - 1.0).abs());
+    let m = v - c;
+    let (r, g, b) = match h {
+        h if h < 60.0 => (c, x, 0.0),
+        h if h < 120.0 => (x, c, 0.0),
+        h if h < 180.0 => (0.0, c, x),
+        h if h < 240.0 => (0.0, x, c),
+        h if h < 300.0 => (x, 0.0, c),
+        _ => (c, 0.0, x),
+    };
+    egui::Color32::from_rgb(
+        ((r + m) * 255.0) as u8,
+        ((g + m) * 255.0) as u8,
+        ((b + m) * 255.0) as u8,
+    )
+}
+
+// `intensity` in 0..=1 mutes the color towards gray, 1.0 keeps it fully vivid
+pub(crate) fn generate_deterministic_color(seed: &str, intensity: f32) -> egui::Color32 {
+    let mut hasher = DefaultHasher::new();
+    seed.hash(&mut hasher);
+    let mut rng = rand::rngs::StdRng::seed_from_u64(hasher.finish());
+    let intensity = intensity.clamp(0.0, 1.0);
+    hsv_to_rgb(
+        rng.random_range(0.0..360.0),
+        rng.random_range(0.7..0.9) * (0.3 + 0.7 * intensity),
+        rng.random_range(0.8..0.95) * (0.75 + 0.25 * intensity),
+    )
+}
+
+pub(crate) fn parse_label_direction(direction: &str) -> Option<LabelDirection> {
+    match direction.to_ascii_lowercase().as_str() {
+        "ltr" => Some(LabelDirection::Ltr),
+        "rtl" => Some(LabelDirection::Rtl),
+        "vertical" => Some(LabelDirection::Vertical),
+        _ => None,
+    }
+}
+
+pub(crate) fn parse_layout(layout: &str) -> Option<Layout> {
+    match layout.to_ascii_lowercase().as_str() {
+        "wheel" => Some(Layout::Wheel),
+        "reel" => Some(Layout::Reel),
+        "picker" => Some(Layout::Picker),
+        _ => None,
+    }
+}
+
+pub(crate) fn parse_boundary_tiebreak(policy: &str) -> Option<BoundaryTiebreak> {
+    match policy.to_ascii_lowercase().as_str() {
+        "lower" => Some(BoundaryTiebreak::Lower),
+        "higher" => Some(BoundaryTiebreak::Higher),
+        "random" => Some(BoundaryTiebreak::Random),
+        _ => None,
+    }
+}
+
+pub(crate) fn parse_segment_sort(sort: &str) -> Option<SegmentSort> {
+    match sort.to_ascii_lowercase().as_str() {
+        "config" => Some(SegmentSort::Config),
+        "label" => Some(SegmentSort::Label),
+        "weight_asc" => Some(SegmentSort::WeightAsc),
+        "weight_desc" => Some(SegmentSort::WeightDesc),
+        _ => None,
+    }
+}
+
+pub(crate) fn parse_odds_format(format: &str) -> Option<OddsFormat> {
+    match format.to_ascii_lowercase().as_str() {
+        "percent" => Some(OddsFormat::Percent),
+        "fraction" => Some(OddsFormat::Fraction),
+        "ratio" => Some(OddsFormat::Ratio),
+        _ => None,
+    }
+}
+
+// Odds of a slice of `weight` out of `total`: the raw weights with a percentage,
+// the fraction in lowest terms, or the weight against the rest
+pub(crate) fn format_odds(weight: u32, total: u32, format: OddsFormat) -> String {
+    match format {
+        OddsFormat::Percent => {
+            let percent = weight as f32 / total as f32 * 100.0;
+            format!("{weight}/{total} ({percent:.1}%)")
+        }
+        OddsFormat::Fraction => {
+            let divisor = gcd(weight, total).max(1);
+            format!("{}/{}", weight / divisor, total / divisor)
+        }
+        OddsFormat::Ratio => {
+            let rest = total - weight;
+            let divisor = gcd(weight, rest).max(1);
+            format!("{}:{}", weight / divisor, rest / divisor)
+        }
+    }
+}
+
+pub(crate) fn gcd(mut a: u32, mut b: u32) -> u32 {
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+    a
+}
+
+pub(crate) fn parse_tick_mode(mode: &str) -> Option<TickMode> {
+    match mode.to_ascii_lowercase().as_str() {
+        "boundary" => Some(TickMode::Boundary),
+        "velocity" => Some(TickMode::Velocity),
+        _ => None,
+    }
+}
+
+pub(crate) fn parse_winner_position(position: &str) -> Option<WinnerPosition> {
+    match position.to_ascii_lowercase().as_str() {
+        "center" => Some(WinnerPosition::Center),
+        "top" => Some(WinnerPosition::Top),
+        "bottom" => Some(WinnerPosition::Bottom),
+        "below_wheel" => Some(WinnerPosition::BelowWheel),
+        _ => None,
+    }
+}
+
+pub(crate) fn parse_theme(theme: &str) -> Option<egui::Theme> {
+    match theme.to_ascii_lowercase().as_str() {
+        "dark" => Some(egui::Theme::Dark),
+        "light" => Some(egui::Theme::Light),
+        _ => None,
+    }
+}
+
+pub(crate) fn parse_mouse_button(button: &str) -> Option<egui::PointerButton> {
+    match button.to_ascii_lowercase().as_str() {
+        "primary" | "left" => Some(egui::PointerButton::Primary),
+        "secondary" | "right" => Some(egui::PointerButton::Secondary),
+        "middle" => Some(egui::PointerButton::Middle),
+        _ => None,
+    }
+}
+
+pub(crate) fn parse_hex_color(hex: &str) -> Option<egui::Color32> {
+    let hex = hex.trim_start_matches('#');
+    if !hex.is_ascii() {
+        return None;
+    }
+    match hex.len() {
+        6 => Some(egui::Color32::from_rgb(
+            u8::from_str_radix(&hex[0..2], 16).ok()?,
+            u8::from_str_radix(&hex[2..4], 16).ok()?,
+            u8::from_str_radix(&hex[4..6], 16).ok()?,
+        )),
+        8 => Some(egui::Color32::from_rgba_unmultiplied(
+            u8::from_str_radix(&hex[0..2], 16).ok()?,
+            u8::from_str_radix(&hex[2..4], 16).ok()?,
+            u8::from_str_radix(&hex[4..6], 16).ok()?,
+            u8::from_str_radix(&hex[6..8], 16).ok()?,
+        )),
+        _ => None,
+    }
+}
+
+// Inverse of `parse_hex_color`, the alpha digits are only written when not opaque
+pub(crate) fn color_to_hex(c: egui::Color32) -> String {
+    let [r, g, b, a] = c.to_srgba_unmultiplied();
+    if a == u8::MAX {
+        format!("#{r:02x}{g:02x}{b:02x}")
+    } else {
+        format!("#{r:02x}{g:02x}{b:02x}{a:02x}")
+    }
+}
+
+// Formats a numeric label with `format`, where `{n}` stands for the number and
+// `{n:,}` groups its thousands with the given separator (any single character).
+// Returns `None` for labels that aren't plain decimal numbers.
+pub(crate) fn format_number_label(label: &str, format: &str) -> Option<String> {
+    let start = format.find("{n")?;
+    let end = start + format[start..].find('}')?;
+    let separator = format[start + 2..end]
+        .strip_prefix(':')
+        .and_then(|s| s.chars().next());
+
+    let number = label.trim();
+    let (sign, digits) = number
+        .strip_prefix('-')
+        .map_or(("", number), |digits| ("-", digits));
+    let (int, frac) = digits
+        .split_once('.')
+        .map_or((digits, None), |(int, frac)| (int, Some(frac)));
+    let all_digits = |s: &str| !s.is_empty() && s.bytes().all(|b| b.is_ascii_digit());
+    if !all_digits(int) || frac.is_some_and(|f| !all_digits(f)) {
+        return None;
+    }
+
+    let mut grouped = String::new();
+    for (i, c) in int.chars().enumerate() {
+        if let Some(separator) = separator
+            && i > 0
+            && (int.len() - i) % 3 == 0
+        {
+            grouped.push(separator);
+        }
+        grouped.push(c);
+    }
+    if let Some(frac) = frac {
+        grouped.push('.');
+        grouped.push_str(frac);
+    }
+
+    Some(format!(
+        "{sign}{}{grouped}{}",
+        &format[..start],
+        &format[end + 1..]
+    ))
+}
+
+// The x coordinates must stay within 0..=1 so the curve is a function of time
+pub(crate) fn is_valid_bezier([x1, y1, x2, y2]: [f32; 4]) -> bool {
+    [x1, y1, x2, y2].iter().all(|v| v.is_finite())
+        && (0.0..=1.0).contains(&x1)
+        && (0.0..=1.0).contains(&x2)
+}
+
+// Evaluates a CSS style cubic-bezier easing at time `t`: solves the x curve for
+// its parameter by Newton's method, falling back to bisection where the slope
+// is too flat, then returns the y coordinate at that parameter.
+pub(crate) fn cubic_bezier([x1, y1, x2, y2]: [f32; 4], t: f32) -> f32 {
+    let t = t.clamp(0.0, 1.0);
+    let curve = |p1: f32, p2: f32, s: f32| {
+        let inv = 1.0 - s;
+        3.0 * inv * inv * s * p1 + 3.0 * inv * s * s * p2 + s * s * s
+    };
+    let slope = |p1: f32, p2: f32, s: f32| {
+        let inv = 1.0 - s;
+        3.0 * inv * inv * p1 + 6.0 * inv * s * (p2 - p1) + 3.0 * s * s * (1.0 - p2)
+    };
+
+    let mut s = t;
+    for _ in 0..8 {
+        let error = curve(x1, x2, s) - t;
+        if error.abs() < 1e-5 {
+            return curve(y1, y2, s);
+        }
+        let d = slope(x1, x2, s);
+        if d.abs() < 1e-6 {
+            break;
+        }
+        s = (s - error / d).clamp(0.0, 1.0);
+    }
+
+    let (mut low, mut high) = (0.0, 1.0);
+    s = t;
+    for _ in 0..32 {
+        let x = curve(x1, x2, s);
+        if (x - t).abs() < 1e-5 {
+            break;
+        }
+        if x < t {
+            low = s;
+        } else {
+            high = s;
+        }
+        s = (low + high) * 0.5;
+    }
+    curve(y1, y2, s)
+}
+
+// `text` cut to at most `max_chars` characters, the last of them an ellipsis
+pub(crate) fn truncate_with_ellipsis(text: &str, max_chars: usize) -> Cow<'_, str> {
+    match text.char_indices().nth(max_chars.saturating_sub(1)) {
+        Some((end, _)) if text.chars().count() > max_chars => {
+            Cow::Owned(format!("{}…", &text[..end]))
+        }
+        _ => Cow::Borrowed(text),
+    }
+}
+
+pub(crate) fn is_bright(c: egui::Color32) -> bool {
+    (0.299 * c.r() as f32 + 0.587 * c.g() as f32 + 0.114 * c.b() as f32) > 128.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn deg_to_rad_converts_quarter_turns() {
+        assert_eq!(deg_to_rad(0.0), 0.0);
+        assert!((deg_to_rad(90.0) - PI / 2.0).abs() < 1e-6);
+        assert!((deg_to_rad(180.0) - PI).abs() < 1e-6);
+        assert!((deg_to_rad(360.0) - 2.0 * PI).abs() < 1e-6);
+    }
+
+    #[test]
+    fn truncates_only_past_the_limit() {
+        assert_eq!(truncate_with_ellipsis("Jackpot", 7), "Jackpot");
+        assert!(matches!(
+            truncate_with_ellipsis("Jackpot", 7),
+            Cow::Borrowed(_)
+        ));
+        assert_eq!(truncate_with_ellipsis("Jackpots", 7), "Jackpo…");
+        assert_eq!(truncate_with_ellipsis("Jackpot", 1), "…");
+        // Counted in characters, not bytes
+        assert_eq!(truncate_with_ellipsis("Ünïcödé", 4), "Ünï…");
+    }
+}
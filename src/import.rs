@@ -0,0 +1,124 @@
+use crate::config::SegmentConfig;
+
+// --- SEGMENT IMPORT ---
+
+// Parses `label,weight,color` rows. A first row naming a `label` column is
+// treated as a header and the columns may then appear in any order; without
+// one the columns are read positionally. Missing weights default to 1 and
+// missing colors are generated. Rows that can't be used are skipped and
+// reported in the returned warnings.
+pub(crate) fn parse_segments_csv(text: &str) -> (Vec<SegmentConfig>, Vec<String>) {
+    let mut reader = csv::ReaderBuilder::new()
+        .has_headers(false)
+        .flexible(true)
+        .trim(csv::Trim::All)
+        .from_reader(text.as_bytes());
+
+    let mut segments = Vec::new();
+    let mut warnings = Vec::new();
+    let mut columns = (Some(0), Some(1), Some(2));
+    let mut first_row = true;
+
+    for record in reader.records() {
+        let record = match record {
+            Ok(record) => record,
+            Err(e) => {
+                warnings.push(format!("csv: {e}, row skipped"));
+                continue;
+            }
+        };
+        let line = record.position().map_or(0, |p| p.line());
+
+        if std::mem::take(&mut first_row) {
+            let position = |name: &str| record.iter().position(|f| f.eq_ignore_ascii_case(name));
+            if let Some(label) = position("label") {
+                columns = (Some(label), position("weight"), position("color"));
+                continue;
+            }
+        }
+
+        let field = |column: Option<usize>| {
+            column
+                .and_then(|c| record.get(c))
+                .filter(|value| !value.is_empty())
+        };
+
+        let Some(label) = field(columns.0) else {
+            warnings.push(format!("csv line {line}: missing label, skipped"));
+            continue;
+        };
+
+        let weight = match field(columns.1).map(str::parse::<u32>) {
+            None => 1,
+            Some(Ok(weight)) => weight,
+            Some(Err(_)) => {
+                warnings.push(format!(
+                    "csv line {line}: invalid weight for '{label}', skipped"
+                ));
+                continue;
+            }
+        };
+
+        segments.push(SegmentConfig {
+            label: label.to_string(),
+            weight,
+            color: field(columns.2).map(str::to_string),
+            font_size: None,
+            is_blank: None,
+            tags: None,
+            image: None,
+            chain_to: None,
+            probability: None,
+        });
+    }
+
+    (segments, warnings)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_a_csv_with_a_header_in_any_column_order() {
+        let csv = "color,label,weight\n#FF0000,Red,3\n,Green,\n#0000FF,Blue\n";
+        let (segments, warnings) = parse_segments_csv(csv);
+
+        assert!(warnings.is_empty(), "{warnings:?}");
+        let parsed: Vec<_> = segments
+            .iter()
+            .map(|s| (s.label.as_str(), s.weight, s.color.as_deref()))
+            .collect();
+        assert_eq!(
+            parsed,
+            [
+                ("Red", 3, Some("#FF0000")),
+                ("Green", 1, None),
+                ("Blue", 1, Some("#0000FF")),
+            ]
+        );
+    }
+
+    #[test]
+    fn reads_columns_positionally_without_a_header() {
+        let (segments, warnings) = parse_segments_csv("Apple, 2, #00FF00\nPear\n");
+        assert!(warnings.is_empty(), "{warnings:?}");
+        assert_eq!(segments.len(), 2);
+        assert_eq!(segments[0].weight, 2);
+        assert_eq!(segments[0].color.as_deref(), Some("#00FF00"));
+        assert_eq!(segments[1].label, "Pear");
+        assert_eq!(segments[1].weight, 1);
+    }
+
+    #[test]
+    fn skips_malformed_rows_with_a_warning() {
+        let csv = "label,weight\nGood,2\nBad,lots\n,4\nAlso good,1\n";
+        let (segments, warnings) = parse_segments_csv(csv);
+
+        let labels: Vec<_> = segments.iter().map(|s| s.label.as_str()).collect();
+        assert_eq!(labels, ["Good", "Also good"]);
+        assert_eq!(warnings.len(), 2, "{warnings:?}");
+        assert!(warnings[0].contains("line 3") && warnings[0].contains("invalid weight"));
+        assert!(warnings[1].contains("line 4") && warnings[1].contains("missing label"));
+    }
+}
@@ -0,0 +1,17 @@
+mod app;
+mod assets;
+mod audio;
+mod command;
+mod config;
+mod geometry;
+mod helpers;
+mod import;
+mod multi;
+#[cfg(feature = "webhook")]
+mod webhook;
+
+pub use app::{OverlayApp, SegmentStats, SpinStats, WinnerResult};
+pub use audio::output_device_names;
+pub use config::{AppConfig, AppConfigBuilder, SegmentConfig, ValidationReport, WheelConfig};
+pub use geometry::{SliceBounds, WheelGeometry};
+pub use multi::MultiWheelApp;
@@ -1,469 +1,341 @@
+use eframe::NativeOptions;
 use eframe::egui;
-use eframe::{App, Frame, NativeOptions};
-use rand::{Rng, SeedableRng};
-use rodio::{OutputStream, OutputStreamHandle, Sink, source::Source};
-use serde::Deserialize;
-use std::collections::hash_map::DefaultHasher;
+use rheel::{AppConfig, MultiWheelApp, OverlayApp, ValidationReport, output_device_names};
 use std::env;
-use std::f32::consts::PI;
 use std::fs;
-use std::hash::{Hash, Hasher};
-use std::time::Duration;
+use std::path::Path;
+use std::process::ExitCode;
 
-// --- CONFIGURATION STRUCTS ---
+const USAGE: &str = "usage: rheel [[--config] <config.toml>] [--preset <name>] [--validate] [--dump-config] [--dump-stats <stats.json>]";
 
-#[derive(Clone, Deserialize, Debug)]
-struct SegmentConfig {
-    label: String,
-    weight: u32,
-    color: Option<String>,
-}
+// --- COMMAND LINE ---
 
-#[derive(Deserialize, Debug)]
-struct AppConfig {
-    spin_duration_ms: f32,
-    center_color: Option<String>,
-    center_radius_ratio: Option<f32>,
-    winner_message: Option<String>,
-    winner_font_size: Option<f32>,
-    label_font_size: Option<f32>,
-    show_segments_borders: Option<bool>,
-    segments: Vec<SegmentConfig>,
+#[derive(Default)]
+struct CliArgs {
+    config_path: Option<String>,
+    preset: Option<String>,
+    validate: bool,
+    dump_config: bool,
+    dump_stats: Option<String>,
+    help: bool,
 }
 
-impl Default for AppConfig {
-    fn default() -> Self {
-        Self {
-            spin_duration_ms: 5000.0,
-            center_color: Some("#202020".to_string()),
-            center_radius_ratio: Some(0.25),
-            winner_message: Some("Winner:\n{label}".to_string()),
-            winner_font_size: Some(40.0),
-            label_font_size: Some(20.0),
-            show_segments_borders: Some(true),
-            segments: vec![
-                SegmentConfig {
-                    label: "1".into(),
-                    weight: 1,
-                    color: None,
-                },
-                SegmentConfig {
-                    label: "2".into(),
-                    weight: 1,
-                    color: None,
-                },
-                SegmentConfig {
-                    label: "3".into(),
-                    weight: 1,
-                    color: None,
-                },
-                SegmentConfig {
-                    label: "4".into(),
-                    weight: 1,
-                    color: None,
-                },
-                SegmentConfig {
-                    label: "5".into(),
-                    weight: 1,
-                    color: None,
-                },
-            ],
+fn parse_args(args: impl IntoIterator<Item = String>) -> Result<CliArgs, String> {
+    let mut cli = CliArgs::default();
+    let mut args = args.into_iter();
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--config" | "-c" => {
+                let path = args.next().ok_or("--config requires a path")?;
+                cli.config_path = Some(path);
+            }
+            "--preset" | "-p" => {
+                let name = args.next().ok_or("--preset requires a name")?;
+                cli.preset = Some(name);
+            }
+            "--validate" => cli.validate = true,
+            "--dump-config" => cli.dump_config = true,
+            "--dump-stats" => {
+                let path = args.next().ok_or("--dump-stats requires a path")?;
+                cli.dump_stats = Some(path);
+            }
+            "--help" | "-h" => cli.help = true,
+            flag if flag.starts_with('-') => return Err(format!("unknown option '{flag}'")),
+            // Positional config path, kept for backwards compatibility
+            _ if cli.config_path.is_none() => cli.config_path = Some(arg),
+            _ => return Err(format!("unexpected argument '{arg}'")),
         }
     }
-}
 
-// --- RUNTIME STRUCTS ---
-
-struct ProcessedSegment {
-    label: String,
-    weight: u32,
-    color: egui::Color32,
+    Ok(cli)
 }
 
-struct OverlayApp {
-    // Spin animation
-    rotation: f32,
-    start_rotation: f32,
-    target_rotation: f32,
-    current_spin_time: f32,
-    spin_duration_ms: f32,
-    is_spinning: bool,
+// A missing or malformed file falls back to the defaults, unless a preset was
+// asked for: that can't be honored, so it's an error like an unknown preset.
+// Segments that can't be imported are an error too, the wheel would be empty.
+fn load_config(path: Option<&str>, preset: Option<&str>) -> Result<AppConfig, String> {
+    let Some(path) = path else {
+        return match preset {
+            Some(name) => Err(format!("--preset '{name}' requires a config path")),
+            None => Ok(AppConfig::default()),
+        };
+    };
+    let loaded = fs::read_to_string(path)
+        .map_err(|e| e.to_string())
+        .and_then(|contents| AppConfig::from_toml(&contents, preset));
+    let mut config = match loaded {
+        Ok(config) => config,
+        Err(e) if preset.is_some() => return Err(format!("{path}: {e}")),
+        Err(_) => AppConfig::default(),
+    };
+    config.base_dir = Path::new(path).parent().map(Path::to_path_buf);
+
+    let warnings = config
+        .load_external_segments()
+        .map_err(|e| format!("{path}: {e}"))?;
+    warnings.iter().for_each(|w| eprintln!("warning: {w}"));
+    if config
+        .wheel_configs()
+        .iter()
+        .any(|wheel| wheel.segments.is_empty())
+    {
+        return Err(format!("{path}: no segments to put on the wheel"));
+    }
+    Ok(config)
+}
 
-    // Audio
-    _audio_stream: OutputStream,
-    audio_handle: OutputStreamHandle,
-    last_segment_index: Option<usize>,
+fn validate(path: &str, preset: Option<&str>) -> ExitCode {
+    let mut config = match fs::read_to_string(path) {
+        Ok(contents) => match AppConfig::from_toml(&contents, preset) {
+            Ok(config) => config,
+            Err(e) => {
+                eprintln!("{path}: failed to parse config:\n{e}");
+                return ExitCode::FAILURE;
+            }
+        },
+        Err(e) => {
+            eprintln!("{path}: failed to read config: {e}");
+            return ExitCode::FAILURE;
+        }
+    };
 
-    // Visuals
-    center_color: egui::Color32,
-    center_radius_ratio: f32,
-    winner_template: String,
-    winner_font_size: f32,
-    label_font_size: f32,
-    show_segments_borders: bool,
+    config.base_dir = Path::new(path).parent().map(Path::to_path_buf);
+    let import = config.load_external_segments();
+    let mut report = config.validate();
+    match import {
+        Ok(warnings) => report.warnings.extend(warnings),
+        Err(e) => report.errors.push(e),
+    }
 
-    // Data
-    segments: Vec<ProcessedSegment>,
-    total_weight: u32,
-    winning_label: Option<String>,
+    for warning in &report.warnings {
+        println!("warning: {warning}");
+    }
+    for error in &report.errors {
+        println!("error: {error}");
+    }
+    println!(
+        "{path}: {} warning(s), {} error(s)",
+        report.warnings.len(),
+        report.errors.len()
+    );
+
+    if report.is_ok() {
+        ExitCode::SUCCESS
+    } else {
+        ExitCode::FAILURE
+    }
 }
 
-impl OverlayApp {
-    fn new(config: AppConfig) -> Self {
-        let total_weight = config.segments.iter().map(|s| s.weight).sum();
-
-        let segments = config
-            .segments
-            .into_iter()
-            .map(|s| {
-                let color = s
-                    .color
-                    .as_deref()
-                    .and_then(parse_hex_color)
-                    .unwrap_or_else(|| generate_deterministic_color(&s.label));
-
-                ProcessedSegment {
-                    label: s.label,
-                    weight: s.weight,
-                    color,
-                }
+fn load_icon(path: &Path) -> Option<egui::IconData> {
+    match image::open(path) {
+        Ok(image) => {
+            let rgba = image.to_rgba8();
+            Some(egui::IconData {
+                width: rgba.width(),
+                height: rgba.height(),
+                rgba: rgba.into_raw(),
             })
-            .collect();
-
-        let center_color = config
-            .center_color
-            .as_deref()
-            .and_then(parse_hex_color)
-            .unwrap_or(egui::Color32::from_gray(32));
-
-        let center_radius_ratio = config.center_radius_ratio.unwrap_or(0.2).clamp(0.0, 0.8);
-
-        // Process winner configuration
-        let winner_template = config
-            .winner_message
-            .unwrap_or_else(|| "Winner:\n{label}".to_string());
-
-        let winner_font_size = config.winner_font_size.unwrap_or(40.0);
-        let label_font_size = config.label_font_size.unwrap_or(20.0);
-        let show_segments_borders = config.show_segments_borders.unwrap_or(true);
-
-        let mut rng = rand::rng();
-
-        // Initialize Audio System
-        let (_stream, stream_handle) =
-            OutputStream::try_default().expect("Failed to initialize audio");
-
-        Self {
-            rotation: rng.random_range(0.0..2.0 * PI),
-            start_rotation: 0.0,
-            target_rotation: 0.0,
-            current_spin_time: 0.0,
-            spin_duration_ms: config.spin_duration_ms,
-            is_spinning: false,
-
-            _audio_stream: _stream,
-            audio_handle: stream_handle,
-            last_segment_index: None,
-
-            center_color,
-            center_radius_ratio,
-            winner_template,
-            winner_font_size,
-            label_font_size,
-            show_segments_borders,
-            segments,
-            total_weight,
-            winning_label: None,
+        }
+        Err(e) => {
+            eprintln!(
+                "warning: failed to load window icon '{}': {e}",
+                path.display()
+            );
+            None
         }
     }
+}
 
-    fn start_spin(&mut self) {
-        let mut rng = rand::rng();
-
-        self.is_spinning = true;
-        self.current_spin_time = 0.0;
-        self.start_rotation = self.rotation;
-        self.winning_label = None;
-        self.last_segment_index = None;
-
-        let extra_spins = rng.random_range(10.0..14.0);
-        let random_offset = rng.random_range(0.0..2.0 * PI);
-
-        self.target_rotation = self.rotation + extra_spins * 2.0 * PI + random_offset;
+// --- OVERLAY PLACEMENT ---
+
+#[cfg(all(target_os = "linux", feature = "layer-shell"))]
+fn parse_overlay_anchor(name: &str) -> Option<egui::Align2> {
+    match name.to_ascii_lowercase().as_str() {
+        "top-left" => Some(egui::Align2::LEFT_TOP),
+        "top" => Some(egui::Align2::CENTER_TOP),
+        "top-right" => Some(egui::Align2::RIGHT_TOP),
+        "left" => Some(egui::Align2::LEFT_CENTER),
+        "center" => Some(egui::Align2::CENTER_CENTER),
+        "right" => Some(egui::Align2::RIGHT_CENTER),
+        "bottom-left" => Some(egui::Align2::LEFT_BOTTOM),
+        "bottom" => Some(egui::Align2::CENTER_BOTTOM),
+        "bottom-right" => Some(egui::Align2::RIGHT_BOTTOM),
+        _ => None,
     }
+}
 
-    fn play_tick_sound(&self) {
-        if let Ok(sink) = Sink::try_new(&self.audio_handle) {
-            let mut rng = rand::rng();
+// Hints for compositors and window managers to treat the window as an overlay.
+// The app id lets Wayland compositor rules pick it out, since winit can't ask
+// for a layer-shell surface itself.
+#[cfg(all(target_os = "linux", feature = "layer-shell"))]
+fn overlay_viewport(viewport: egui::ViewportBuilder) -> egui::ViewportBuilder {
+    viewport
+        .with_app_id("rheel-overlay")
+        .with_window_type(egui::X11WindowType::Utility)
+        .with_taskbar(false)
+}
 
-            let pitch_jitter = rng.random_range(550.0..650.0);
-            let volume_jitter = rng.random_range(0.0005..0.0015);
+// Moves the window to its anchor once the monitor size is known, which is only
+// after the first frame. Wayland compositors place windows themselves and
+// ignore the move.
+#[cfg(all(target_os = "linux", feature = "layer-shell"))]
+struct AnchoredApp {
+    inner: Box<dyn eframe::App>,
+    anchor: Option<egui::Align2>,
+}
 
-            let source = rodio::source::SineWave::new(pitch_jitter)
-                .take_duration(Duration::from_millis(30))
-                .amplify(volume_jitter);
+#[cfg(all(target_os = "linux", feature = "layer-shell"))]
+impl eframe::App for AnchoredApp {
+    fn clear_color(&self, visuals: &egui::Visuals) -> [f32; 4] {
+        self.inner.clear_color(visuals)
+    }
 
-            sink.append(source);
-            sink.detach();
+    fn update(&mut self, ctx: &egui::Context, frame: &mut eframe::Frame) {
+        if let Some(anchor) = self.anchor
+            && let Some((monitor, outer)) =
+                ctx.input(|i| Some((i.viewport().monitor_size?, i.viewport().outer_rect?)))
+        {
+            let screen = egui::Rect::from_min_size(egui::Pos2::ZERO, monitor);
+            let position = anchor.align_size_within_rect(outer.size(), screen).min;
+            ctx.send_viewport_cmd(egui::ViewportCommand::OuterPosition(position));
+            self.anchor = None;
         }
+        self.inner.update(ctx, frame);
     }
 
-    fn get_current_segment_info(&self) -> (usize, &str, egui::Color32) {
-        let normalized_rotation = self.rotation.rem_euclid(2.0 * PI);
-        let pointer_angle = 1.5 * PI;
-
-        let mut hit_angle = pointer_angle - normalized_rotation;
-        hit_angle = hit_angle.rem_euclid(2.0 * PI);
+    fn on_exit(&mut self, gl: Option<&eframe::glow::Context>) {
+        self.inner.on_exit(gl);
+    }
+}
 
-        let mut cursor = 0.0;
-        for (i, seg) in self.segments.iter().enumerate() {
-            let width = (seg.weight as f32 / self.total_weight as f32) * 2.0 * PI;
-            if hit_angle >= cursor && hit_angle < cursor + width {
-                return (i, &seg.label, seg.color);
-            }
-            cursor += width;
+fn main() -> eframe::Result<ExitCode> {
+    let cli = match parse_args(env::args().skip(1)) {
+        Ok(cli) => cli,
+        Err(message) => {
+            eprintln!("{message}\n{USAGE}");
+            return Ok(ExitCode::FAILURE);
         }
+    };
 
-        let last_idx = self.segments.len() - 1;
-        let last = &self.segments[last_idx];
-        (last_idx, &last.label, last.color)
+    if cli.help {
+        println!("{USAGE}");
+        return Ok(ExitCode::SUCCESS);
     }
-}
 
-impl App for OverlayApp {
-    fn clear_color(&self, _: &egui::Visuals) -> [f32; 4] {
-        [0.0, 0.0, 0.0, 0.0]
+    if cli.validate {
+        let Some(path) = &cli.config_path else {
+            eprintln!("--validate requires a config path");
+            return Ok(ExitCode::FAILURE);
+        };
+        return Ok(validate(path, cli.preset.as_deref()));
     }
 
-    fn update(&mut self, ctx: &egui::Context, _: &mut Frame) {
-        if ctx.input(|i| i.key_pressed(egui::Key::Escape)) {
-            ctx.send_viewport_cmd(egui::ViewportCommand::Close);
+    let config = match load_config(cli.config_path.as_deref(), cli.preset.as_deref()) {
+        Ok(config) => config,
+        Err(e) => {
+            eprintln!("error: {e}");
+            return Ok(ExitCode::FAILURE);
         }
+    };
 
-        if ctx.input(|i| i.key_pressed(egui::Key::Space)) && !self.is_spinning {
-            self.start_spin();
-        }
-
-        let dt = ctx.input(|i| i.stable_dt).min(0.1);
-
-        if self.is_spinning {
-            self.current_spin_time += dt;
-            let duration = self.spin_duration_ms / 1000.0;
-            let t = (self.current_spin_time / duration).clamp(0.0, 1.0);
-
-            let eased = 1.0 - (1.0 - t).powi(5);
-
-            self.rotation =
-                self.start_rotation + eased * (self.target_rotation - self.start_rotation);
-
-            // --- AUDIO TRIGGER LOGIC ---
-
-            let (current_index, label_text) = {
-                let (idx, lbl, _) = self.get_current_segment_info();
-                (idx, lbl.to_string())
-            };
-
-            if self.last_segment_index.is_none() {
-                self.last_segment_index = Some(current_index);
-            } else if let Some(last_index) = self.last_segment_index {
-                if last_index != current_index {
-                    self.play_tick_sound();
-                    self.last_segment_index = Some(current_index);
-                }
+    // Prints the effective config, with imported segments inlined
+    if cli.dump_config {
+        return Ok(match config.to_toml() {
+            Ok(toml) => {
+                print!("{toml}");
+                ExitCode::SUCCESS
             }
-
-            if t >= 1.0 {
-                self.is_spinning = false;
-                self.winning_label = Some(label_text);
+            Err(e) => {
+                eprintln!("error: {e}");
+                ExitCode::FAILURE
             }
-
-            ctx.request_repaint();
-        }
-
-        // --- DRAWING ---
-        egui::CentralPanel::default()
-            .frame(egui::Frame::NONE.fill(egui::Color32::TRANSPARENT))
-            .show(ctx, |ui| {
-                let rect = ui.max_rect();
-                let center = rect.center();
-                let outer_radius = 250.0;
-                let inner_radius = outer_radius * self.center_radius_ratio;
-
-                let (_, _, pointer_color) = self.get_current_segment_info();
-
-                if let Some(pos) = ctx.input(|i| i.pointer.interact_pos()) {
-                    if pos.distance(center) <= outer_radius
-                        && ctx.input(|i| i.pointer.primary_clicked())
-                        && !self.is_spinning
-                    {
-                        self.start_spin();
-                    }
-                }
-
-                ui.painter().circle_filled(
-                    center,
-                    outer_radius + 5.0,
-                    egui::Color32::from_black_alpha(220),
-                );
-
-                let mut angle = self.rotation;
-
-                for seg in &self.segments {
-                    let width = (seg.weight as f32 / self.total_weight as f32) * 2.0 * PI;
-                    let end = angle + width;
-                    let steps = (width * 15.0).max(3.0) as usize;
-                    let mut points = vec![center];
-
-                    for i in 0..=steps {
-                        let a = angle + (i as f32 / steps as f32) * width;
-                        points.push(egui::pos2(
-                            center.x + outer_radius * a.cos(),
-                            center.y + outer_radius * a.sin(),
-                        ));
-                    }
-
-                    let stroke = if self.show_segments_borders {
-                        egui::Stroke::new(1.0, egui::Color32::BLACK)
-                    } else {
-                        egui::Stroke::new(1.0, seg.color)
-                    };
-
-                    ui.painter()
-                        .add(egui::Shape::convex_polygon(points, seg.color, stroke));
-
-                    // Text drawing logic - skips if size is 0
-                    if self.label_font_size > 0.0 {
-                        let text_r = inner_radius + (outer_radius - inner_radius) * 0.5;
-                        let text_a = angle + width * 0.5;
-                        let text_pos = egui::pos2(
-                            center.x + text_r * text_a.cos(),
-                            center.y + text_r * text_a.sin(),
-                        );
-
-                        ui.painter().text(
-                            text_pos,
-                            egui::Align2::CENTER_CENTER,
-                            &seg.label,
-                            egui::FontId::proportional(self.label_font_size),
-                            if is_bright(seg.color) {
-                                egui::Color32::BLACK
-                            } else {
-                                egui::Color32::WHITE
-                            },
-                        );
-                    }
-
-                    angle = end;
-                }
-
-                ui.painter().circle(
-                    center,
-                    inner_radius,
-                    self.center_color,
-                    egui::Stroke::new(2.0, egui::Color32::BLACK),
-                );
-
-                ui.painter().add(egui::Shape::convex_polygon(
-                    vec![
-                        egui::pos2(center.x - 15.0, center.y - outer_radius - 20.0),
-                        egui::pos2(center.x + 15.0, center.y - outer_radius - 20.0),
-                        egui::pos2(center.x, center.y - outer_radius + 10.0),
-                    ],
-                    pointer_color,
-                    egui::Stroke::new(2.0, egui::Color32::BLACK),
-                ));
-
-                if let Some(winner) = &self.winning_label {
-                    ui.centered_and_justified(|ui| {
-                        let message = self.winner_template.replace("{label}", winner);
-
-                        ui.label(
-                            egui::RichText::new(message)
-                                .size(self.winner_font_size)
-                                .strong()
-                                .background_color(egui::Color32::from_black_alpha(200))
-                                .color(egui::Color32::WHITE),
-                        );
-                    });
-                }
-            });
+        });
     }
-}
-
-// --- HELPERS ---
 
-fn hsv_to_rgb(h: f32, s: f32, v: f32) -> egui::Color32 {
-    let c = v * s;
-    let x = c * (1.0 - ((h / 60.0) % 2.0 - 1.0).abs());
-    let m = v - c;
-    let (r, g, b) = match h {
-        h if h < 60.0 => (c, x, 0.0),
-        h if h < 120.0 => (x, c, 0.0),
-        h if h < 180.0 => (0.0, c, x),
-        h if h < 240.0 => (0.0, x, c),
-        h if h < 300.0 => (x, 0.0, c),
-        _ => (c, 0.0, x),
+    let wheel_count = config.wheels.as_ref().map_or(1, Vec::len).max(1);
+    let mut viewport = egui::ViewportBuilder::default()
+        .with_decorations(false)
+        .with_transparent(true)
+        .with_always_on_top()
+        .with_resizable(false)
+        .with_inner_size([600.0 * wheel_count as f32, 600.0]);
+    #[cfg(all(target_os = "linux", feature = "layer-shell"))]
+    let anchor = {
+        viewport = overlay_viewport(viewport);
+        if env::var_os("WAYLAND_DISPLAY").is_some() && config.overlay_anchor.is_some() {
+            eprintln!(
+                "warning: Wayland compositors don't let windows place themselves, \
+                 anchor the 'rheel-overlay' app id with a compositor rule instead"
+            );
+        }
+        config.overlay_anchor.as_deref().and_then(|name| {
+            let anchor = parse_overlay_anchor(name);
+            if anchor.is_none() {
+                eprintln!("warning: unknown overlay_anchor '{name}', the window is not moved");
+            }
+            anchor
+        })
     };
-    egui::Color32::from_rgb(
-        ((r + m) * 255.0) as u8,
-        ((g + m) * 255.0) as u8,
-        ((b + m) * 255.0) as u8,
-    )
-}
-
-fn generate_deterministic_color(seed: &str) -> egui::Color32 {
-    let mut hasher = DefaultHasher::new();
-    seed.hash(&mut hasher);
-    let mut rng = rand::rngs::StdRng::seed_from_u64(hasher.finish());
-    hsv_to_rgb(
-        rng.random_range(0.0..360.0),
-        rng.random_range(0.7..0.9),
-        rng.random_range(0.8..0.95),
-    )
-}
-
-fn parse_hex_color(hex: &str) -> Option<egui::Color32> {
-    let hex = hex.trim_start_matches('#');
-    if hex.len() == 6 {
-        Some(egui::Color32::from_rgb(
-            u8::from_str_radix(&hex[0..2], 16).ok()?,
-            u8::from_str_radix(&hex[2..4], 16).ok()?,
-            u8::from_str_radix(&hex[4..6], 16).ok()?,
-        ))
-    } else {
-        None
+    #[cfg(not(all(target_os = "linux", feature = "layer-shell")))]
+    if config.overlay_anchor.is_some() {
+        eprintln!("warning: overlay_anchor needs the layer-shell feature on Linux and is ignored");
     }
-}
-
-fn is_bright(c: egui::Color32) -> bool {
-    (0.299 * c.r() as f32 + 0.587 * c.g() as f32 + 0.114 * c.b() as f32) > 128.0
-}
 
-fn load_config() -> AppConfig {
-    let args: Vec<String> = env::args().collect();
-    if args.len() < 2 {
-        return AppConfig::default();
+    if let Some(icon) = config
+        .window_icon
+        .as_ref()
+        .and_then(|path| load_icon(&config.asset_path(path)))
+    {
+        viewport = viewport.with_icon(icon);
     }
-    fs::read_to_string(&args[1])
-        .ok()
-        .and_then(|c| toml::from_str(&c).ok())
-        .unwrap_or_default()
-}
-
-fn main() -> eframe::Result<()> {
-    let config = load_config();
     let options = NativeOptions {
-        viewport: egui::ViewportBuilder::default()
-            .with_decorations(false)
-            .with_transparent(true)
-            .with_always_on_top()
-            .with_resizable(false)
-            .with_inner_size([600.0, 600.0]),
+        viewport,
+        multisampling: if config.high_quality == Some(true) {
+            4
+        } else {
+            0
+        },
         ..Default::default()
     };
+
+    // Listed once at startup, so the names can be copied into `audio_device`
+    eprintln!("audio output devices: {}", output_device_names().join(", "));
+
+    // Built before the window opens, so strict_assets fails without one
+    let app: Result<Box<dyn eframe::App>, ValidationReport> =
+        match (config.wheels.is_some(), cli.dump_stats) {
+            (true, Some(path)) => {
+                MultiWheelApp::try_new(config).map(|app| Box::new(app.with_stats_path(path)) as _)
+            }
+            (true, None) => MultiWheelApp::try_new(config).map(|app| Box::new(app) as _),
+            (false, Some(path)) => {
+                OverlayApp::try_new(config).map(|app| Box::new(app.with_stats_path(path)) as _)
+            }
+            (false, None) => OverlayApp::try_new(config).map(|app| Box::new(app) as _),
+        };
+    let app = match app {
+        Ok(app) => app,
+        Err(report) => {
+            for warning in &report.warnings {
+                eprintln!("warning: {warning}");
+            }
+            for error in &report.errors {
+                eprintln!("error: {error}");
+            }
+            eprintln!("error: strict_assets is set and asset files are missing");
+            return Ok(ExitCode::FAILURE);
+        }
+    };
+
     eframe::run_native(
         "rheel",
         options,
-        Box::new(|_| Ok(Box::new(OverlayApp::new(config)))),
-    )
+        Box::new(|_| {
+            #[cfg(all(target_os = "linux", feature = "layer-shell"))]
+            let app = Box::new(AnchoredApp { inner: app, anchor });
+            Ok(app)
+        }),
+    )?;
+    Ok(ExitCode::SUCCESS)
 }
@@ -0,0 +1,149 @@
+use crate::app::{OverlayApp, SpinStats, write_stats_json};
+use crate::audio::open_output_stream;
+use crate::config::{AppConfig, ValidationReport};
+use eframe::egui;
+use eframe::{App, Frame};
+
+// --- SIDE-BY-SIDE WHEELS ---
+
+const WHEEL_KEYS: [egui::Key; 9] = [
+    egui::Key::Num1,
+    egui::Key::Num2,
+    egui::Key::Num3,
+    egui::Key::Num4,
+    egui::Key::Num5,
+    egui::Key::Num6,
+    egui::Key::Num7,
+    egui::Key::Num8,
+    egui::Key::Num9,
+];
+
+/// Several independent wheels in one window, laid out left to right, one per
+/// entry of [`AppConfig::wheels`]. Space spins every idle wheel at once and
+/// the number keys spin a single wheel. Each wheel shows its own winner.
+/// A winning segment with `chain_to` spins the named wheel next.
+pub struct MultiWheelApp {
+    wheels: Vec<OverlayApp>,
+    names: Vec<Option<String>>,
+    stats_path: Option<String>,
+    // Chained spins (target wheel, chain depth) waiting for a busy wheel
+    pending_chains: Vec<(usize, u32)>,
+}
+
+impl MultiWheelApp {
+    /// Builds the wheels, printing any config problems.
+    ///
+    /// # Panics
+    ///
+    /// With `strict_assets` set, if any asset file is missing. Use
+    /// [`Self::try_new`] to handle that instead.
+    pub fn new(config: AppConfig) -> Self {
+        Self::try_new(config).unwrap_or_else(|report| {
+            report.print();
+            panic!("strict_assets is set and asset files are missing");
+        })
+    }
+
+    /// Builds the wheels, printing any config problems, or returns the
+    /// validation report when `strict_assets` is set and asset files are missing.
+    pub fn try_new(config: AppConfig) -> Result<Self, ValidationReport> {
+        config.validate_strict()?.print();
+        // One output stream for every wheel, kept open by the first
+        let (mut stream, handle) = open_output_stream(config.audio_device.as_deref()).unzip();
+        Ok(Self {
+            names: config
+                .wheels
+                .iter()
+                .flatten()
+                .map(|wheel| wheel.name.clone())
+                .collect(),
+            wheels: config
+                .wheel_configs()
+                .into_iter()
+                .map(|wheel| OverlayApp::with_audio(wheel, stream.take(), handle.clone()))
+                .collect(),
+            stats_path: None,
+            pending_chains: Vec::new(),
+        })
+    }
+
+    /// Writes the [`SpinStats`] of every wheel to `path` as a JSON array when
+    /// the app exits.
+    pub fn with_stats_path(mut self, path: impl Into<String>) -> Self {
+        self.stats_path = Some(path.into());
+        self
+    }
+}
+
+impl App for MultiWheelApp {
+    fn clear_color(&self, _: &egui::Visuals) -> [f32; 4] {
+        [0.0, 0.0, 0.0, 0.0]
+    }
+
+    fn update(&mut self, ctx: &egui::Context, _: &mut Frame) {
+        for (wheel, key) in self.wheels.iter_mut().zip(WHEEL_KEYS) {
+            if ctx.input(|i| i.key_pressed(key)) {
+                wheel.spin();
+            }
+        }
+
+        egui::CentralPanel::default()
+            .frame(egui::Frame::NONE.fill(egui::Color32::TRANSPARENT))
+            .show(ctx, |ui| {
+                let rect = ui.max_rect();
+                let width = rect.width() / self.wheels.len().max(1) as f32;
+
+                for (i, wheel) in self.wheels.iter_mut().enumerate() {
+                    let column = egui::Rect::from_min_size(
+                        rect.min + egui::vec2(width * i as f32, 0.0),
+                        egui::vec2(width, rect.height()),
+                    );
+                    let mut ui = ui.new_child(egui::UiBuilder::new().max_rect(column).id_salt(i));
+                    wheel.show(ctx, &mut ui);
+                }
+            });
+
+        for i in 0..self.wheels.len() {
+            let Some((target, depth)) = self.wheels[i].take_chain_request() else {
+                continue;
+            };
+            match self.names.iter().position(|n| n.as_ref() == Some(&target)) {
+                Some(next) => self.pending_chains.push((next, depth)),
+                None => eprintln!("warning: chain_to '{target}' names no wheel"),
+            }
+        }
+
+        // A wheel still spinning, paused or cooling down takes the chained spin
+        // once it can, so the chain's last winner really comes from its last wheel
+        let wheels = &mut self.wheels;
+        let names = &self.names;
+        self.pending_chains.retain(|&(next, depth)| {
+            if wheels[next].spin_chained(depth) {
+                return false;
+            }
+            if wheels[next].spins_exhausted() {
+                let name = names[next].as_deref().unwrap_or("?");
+                eprintln!("warning: chain_to '{name}' is dropped, that wheel is out of spins");
+                return false;
+            }
+            ctx.request_repaint();
+            true
+        });
+    }
+
+    fn on_exit(&mut self, _: Option<&eframe::glow::Context>) {
+        let Some(path) = &self.stats_path else {
+            return;
+        };
+        let stats: Vec<SpinStats> = self
+            .wheels
+            .iter()
+            .zip(&self.names)
+            .map(|(wheel, name)| SpinStats {
+                wheel: name.clone(),
+                ..wheel.stats()
+            })
+            .collect();
+        write_stats_json(path, &stats);
+    }
+}
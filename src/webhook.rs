@@ -0,0 +1,30 @@
+use crate::app::WinnerResult;
+use serde::Serialize;
+use std::thread;
+use std::time::Duration;
+
+// --- RESULT WEBHOOK ---
+
+const WEBHOOK_TIMEOUT: Duration = Duration::from_secs(5);
+
+#[derive(Serialize)]
+struct WebhookPayload<'a> {
+    spin: u32,
+    winners: &'a [WinnerResult],
+}
+
+// Sends the results of a spin as JSON from a background thread, so a slow or
+// unreachable endpoint never stalls the UI. Failures are only logged.
+pub(crate) fn post_results(url: &str, spin: u32, winners: Vec<WinnerResult>) {
+    let url = url.to_string();
+    thread::spawn(move || {
+        let agent = ureq::AgentBuilder::new().timeout(WEBHOOK_TIMEOUT).build();
+        let payload = WebhookPayload {
+            spin,
+            winners: &winners,
+        };
+        if let Err(e) = agent.post(&url).send_json(&payload) {
+            eprintln!("warning: webhook POST to '{url}' failed: {e}");
+        }
+    });
+}